@@ -0,0 +1,107 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+	Integer(i64),
+	Text(String)
+}
+
+impl fmt::Display for Answer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Answer::Integer(value) => write!(f, "{}", value),
+			Answer::Text(text) => write!(f, "{}", text)
+		}
+	}
+}
+
+macro_rules! answer_from_integer {
+	($($integer:ty),*) => {
+		$(impl From<$integer> for Answer {
+			fn from(value: $integer) -> Self {
+				match i64::try_from(value) {
+					Ok(value) => Answer::Integer(value),
+					Err(_) => Answer::Text(value.to_string())
+				}
+			}
+		})*
+	}
+}
+
+answer_from_integer!(u32, u64, usize, i32, i64);
+
+/// What a part may return: anything that converts into an `Answer`, or a `Result` of it for
+/// parts that can fail on inputs the parser accepted.
+pub trait IntoAnswer {
+	fn into_answer(self) -> Result<Answer, Error>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+	fn into_answer(self) -> Result<Answer, Error> {
+		Ok(self.into())
+	}
+}
+
+impl<T: Into<Answer>> IntoAnswer for Result<T, Error> {
+	fn into_answer(self) -> Result<Answer, Error> {
+		self.map(|answer| answer.into())
+	}
+}
+
+impl FromStr for Answer {
+	type Err = Infallible;
+
+	fn from_str(text: &str) -> Result<Self, Infallible> {
+		Ok(match text.parse::<i64>() {
+			Ok(value) => Answer::Integer(value),
+			Err(_) => Answer::Text(text.to_string())
+		})
+	}
+}
+
+impl From<String> for Answer {
+	fn from(text: String) -> Self {
+		Answer::Text(text)
+	}
+}
+
+impl From<&str> for Answer {
+	fn from(text: &str) -> Self {
+		Answer::Text(text.to_string())
+	}
+}
+
+#[cfg(test)]
+mod answer_tests {
+	use super::*;
+
+#[test]
+	fn integers_and_text_display_plainly() {
+		assert_eq!(Answer::from(4361_u32).to_string(), "4361");
+		assert_eq!(Answer::from(-7_i64).to_string(), "-7");
+		assert_eq!(Answer::from("EZFCHJAB").to_string(), "EZFCHJAB");
+	}
+
+#[test]
+	fn parse_round_trips_display() {
+		assert_eq!("-12".parse::<Answer>(), Ok(Answer::Integer(-12)));
+		assert_eq!("EZFCHJAB".parse::<Answer>(), Ok(Answer::from("EZFCHJAB")));
+	}
+
+#[test]
+	fn integer_types_compare_equal() {
+		assert_eq!(Answer::from(46_usize), Answer::from(46_u64));
+		assert_ne!(Answer::from(46_usize), Answer::from("46"));
+	}
+
+#[test]
+	fn integers_beyond_i64_keep_their_digits() {
+		assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+		assert_eq!(Answer::from(u64::MAX), Answer::from("18446744073709551615"));
+		assert_eq!(Answer::from(i64::MAX as u64), Answer::Integer(i64::MAX));
+	}
+}
@@ -0,0 +1,83 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::Result;
+use crate::input::Source;
+use crate::solution::Runnable;
+
+#[derive(Debug, PartialEq)]
+pub struct Statistics {
+	pub min: Duration,
+	pub median: Duration,
+	pub mean: Duration
+}
+
+impl Statistics {
+	pub fn new(samples: &[Duration]) -> Self {
+		let mut sorted = samples.to_vec();
+		sorted.sort();
+		let middle = sorted.len() / 2;
+		let median = if sorted.len().is_multiple_of(2) {
+			(sorted[middle - 1] + sorted[middle]) / 2
+		} else {
+			sorted[middle]
+		};
+		Statistics {
+			min: sorted[0],
+			median,
+			mean: sorted.iter().sum::<Duration>() / sorted.len() as u32
+		}
+	}
+}
+
+impl fmt::Display for Statistics {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "min {:.3?}, median {:.3?}, mean {:.3?}", self.min, self.median, self.mean)
+	}
+}
+
+pub struct Report {
+	pub parse: Statistics,
+	pub solve: Statistics
+}
+
+pub fn bench(solution: &dyn Runnable, part: usize, source: &Source, runs: usize) -> Result<Report> {
+	let mut parse_times = Vec::with_capacity(runs);
+	let mut solve_times = Vec::with_capacity(runs);
+	for _ in 0..runs.max(1) {
+		let run = solution.run_part(part, source)?;
+		parse_times.push(run.parse_time);
+		solve_times.push(run.solve_time);
+	}
+	Ok(Report {
+		parse: Statistics::new(&parse_times),
+		solve: Statistics::new(&solve_times)
+	})
+}
+
+#[cfg(test)]
+mod bench_tests {
+	use super::*;
+
+	fn ms(values: &[u64]) -> Vec<Duration> {
+		values.iter().map(|v| Duration::from_millis(*v)).collect()
+	}
+
+#[test]
+	fn statistics_odd_number_of_samples() {
+		assert_eq!(Statistics::new(&ms(&[9, 1, 5])), Statistics {
+			min: Duration::from_millis(1),
+			median: Duration::from_millis(5),
+			mean: Duration::from_millis(5)
+		});
+	}
+
+#[test]
+	fn statistics_even_number_of_samples() {
+		assert_eq!(Statistics::new(&ms(&[4, 1, 2, 9])), Statistics {
+			min: Duration::from_millis(1),
+			median: Duration::from_millis(3),
+			mean: Duration::from_millis(4)
+		});
+	}
+}
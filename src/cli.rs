@@ -0,0 +1,285 @@
+use crate::interval::Interval;
+
+pub const USAGE: &str = "\
+Usage: advent_of_code_2023 [verify | bench | fetch | submit | new-day | render] [all | DAY...] [--day DAY]
+                           [--part PART] [--input FILE] [--time] [--runs N] [--html] [--verbose]
+                           [--trace VALUE | START..END] [--data DIR] [--all-inputs]
+
+  verify          Check answers against DIR/dayN/answers.toml, including every
+                  named input DIR/dayN/inputs/NAME.txt under [inputs.NAME]
+  bench           Repeatedly solve the input and report min/median/mean timings
+  fetch           Download missing puzzle inputs, the session token is read from
+                  AOC_SESSION or .aoc.toml and the server from AOC_BASE_URL
+  submit          Submit the answer of one day and part, attempts are recorded in
+                  DIR/dayN/submissions.tsv
+  new-day         Generate and register src/dayN.rs and DIR/dayN/
+  render          Print a day's parsed input with its solution highlighted
+  all             Run every registered day (the default)
+  DAY             Day to run, either as `5` or `day5`
+  --day DAY       Day to run, may be repeated
+  --part PART     Only run part 1 or part 2
+  --input FILE    Solve FILE instead of the day's example and input files,
+                  use `-` to read the input from stdin
+  --time          Report parse and solve times of every run
+  --runs N        Number of repetitions in bench mode (default 10)
+  --html          Render an HTML page instead of coloured terminal output
+  --verbose       Trace intermediate steps on stderr, repeat for more detail
+  --trace RANGE   Only trace the values in RANGE, a single value or the
+                  half-open START..END, implies --verbose
+  --data DIR      Directory holding the dayN/ puzzle data, defaults to
+                  AOC_DATA_ROOT, data_root in .aoc.toml or the crate's problem_data
+  --all-inputs    Also solve every named input DIR/dayN/inputs/NAME.txt
+  --help          Show this message";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+	Run,
+	Verify,
+	Bench,
+	Fetch,
+	Submit,
+	NewDay,
+	Render
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+	pub command: Command,
+	pub days: Vec<usize>,
+	pub parts: Vec<usize>,
+	pub input: Option<String>,
+	pub time: bool,
+	pub runs: usize,
+	pub html: bool,
+	pub verbosity: usize,
+	pub trace: Option<Interval>,
+	pub data_root: Option<String>,
+	pub all_inputs: bool,
+	pub help: bool
+}
+
+impl Options {
+	pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, String> {
+		let mut options = Options {
+			command: Command::Run,
+			days: Vec::new(),
+			parts: vec![1, 2],
+			input: None,
+			time: false,
+			runs: 10,
+			html: false,
+			verbosity: 0,
+			trace: None,
+			data_root: None,
+			all_inputs: false,
+			help: false
+		};
+		let mut run_all = false;
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"--help" | "-h" => options.help = true,
+				"all" => run_all = true,
+				"verify" => options.command = Command::Verify,
+				"bench" => options.command = Command::Bench,
+				"fetch" => options.command = Command::Fetch,
+				"submit" => options.command = Command::Submit,
+				"new-day" => options.command = Command::NewDay,
+				"render" => options.command = Command::Render,
+				"--time" | "-t" => options.time = true,
+				"--html" => options.html = true,
+				"--all-inputs" => options.all_inputs = true,
+				"--verbose" | "-v" => options.verbosity += 1,
+				"-vv" => options.verbosity += 2,
+				"--trace" => options.trace = Some(parse_trace(&value(&arg, args.next())?)?),
+				"--runs" | "-r" => options.runs = parse_runs(&value(&arg, args.next())?)?,
+				"--day" | "-d" => options.days.push(parse_day(&value(&arg, args.next())?)?),
+				"--part" | "-p" => options.parts = vec![parse_part(&value(&arg, args.next())?)?],
+				"--input" | "-i" => options.input = Some(value(&arg, args.next())?),
+				"--data" => options.data_root = Some(value(&arg, args.next())?),
+				flag if flag.starts_with('-') => return Err(format!("Unknown option: {}", flag)),
+				day => options.days.push(parse_day(day)?)
+			}
+		}
+		if run_all {
+			options.days.clear();
+		}
+		if options.trace.is_some() {
+			options.verbosity = options.verbosity.max(1);
+		}
+		if options.input.is_some() && options.command == Command::Verify {
+			return Err("--input cannot be verified against recorded answers".to_string());
+		}
+		if options.input.is_some() && options.command == Command::Fetch {
+			return Err("--input cannot be fetched".to_string());
+		}
+		if options.command == Command::Submit {
+			if options.input.is_some() {
+				return Err("submit always solves the day's input".to_string());
+			}
+			if options.days.len() != 1 || options.parts.len() != 1 {
+				return Err("submit requires exactly one --day and one --part".to_string());
+			}
+		}
+		if options.command == Command::NewDay && (options.days.len() != 1 || run_all) {
+			return Err("new-day requires exactly one day".to_string());
+		}
+		if options.command == Command::Render && options.days.len() != 1 {
+			return Err("render requires exactly one day".to_string());
+		}
+		if options.html && options.command != Command::Render {
+			return Err("--html only applies to render".to_string());
+		}
+		if options.all_inputs && (options.command != Command::Run || options.input.is_some()) {
+			return Err("--all-inputs only applies to running the day's data files".to_string());
+		}
+		if options.input.is_some() && options.days.len() != 1 {
+			return Err("--input requires exactly one day".to_string());
+		}
+		Ok(options)
+	}
+}
+
+fn value(flag: &str, next: Option<String>) -> Result<String, String> {
+	next.ok_or(format!("Missing value for {}", flag))
+}
+
+fn parse_day(text: &str) -> Result<usize, String> {
+	text.strip_prefix("day")
+		.unwrap_or(text)
+		.parse::<usize>()
+		.map_err(|_| format!("Not a day: {}", text))
+}
+
+fn parse_runs(text: &str) -> Result<usize, String> {
+	match text.parse::<usize>() {
+		Ok(runs) if runs > 0 => Ok(runs),
+		_ => Err(format!("Not a positive number of runs: {}", text))
+	}
+}
+
+fn parse_trace(text: &str) -> Result<Interval, String> {
+	let number = |text: &str| text.parse::<usize>().map_err(|_| format!("Not a trace range: {}", text));
+	match text.split_once("..") {
+		Some((start, end)) => {
+			let (start, end) = (number(start)?, number(end)?);
+			if start < end {
+				Ok(Interval::new(start, end))
+			} else {
+				Err(format!("Empty trace range: {}", text))
+			}
+		},
+		None => Ok(Interval::with_length(number(text)?, 1))
+	}
+}
+
+fn parse_part(text: &str) -> Result<usize, String> {
+	match text {
+		"1" => Ok(1),
+		"2" => Ok(2),
+		_ => Err(format!("Not a part: {}", text))
+	}
+}
+
+#[cfg(test)]
+mod cli_tests {
+	use super::*;
+
+	fn parse(args: &[&str]) -> Result<Options, String> {
+		Options::parse(args.iter().map(|arg| arg.to_string()))
+	}
+
+#[test]
+	fn positional_and_flag_days() {
+		let options = parse(&["day1", "3", "--day", "5", "--part", "2"]).unwrap();
+		assert_eq!(options.days, vec![1, 3, 5]);
+		assert_eq!(options.parts, vec![2]);
+	}
+
+#[test]
+	fn all_selects_every_day() {
+		assert_eq!(parse(&["all"]).unwrap().days, Vec::<usize>::new());
+	}
+
+#[test]
+	fn input_needs_single_day() {
+		assert!(parse(&["--input", "foo.txt"]).is_err());
+		assert!(parse(&["1", "2", "--input", "foo.txt"]).is_err());
+		assert_eq!(parse(&["-d", "2", "-i", "foo.txt"]).unwrap().input,
+				   Some("foo.txt".to_string()));
+	}
+
+#[test]
+	fn verify_command() {
+		let options = parse(&["verify", "5"]).unwrap();
+		assert_eq!(options.command, Command::Verify);
+		assert_eq!(options.days, vec![5]);
+		assert!(parse(&["verify", "5", "--input", "foo.txt"]).is_err());
+	}
+
+#[test]
+	fn bench_command() {
+		let options = parse(&["bench", "--runs", "25", "6"]).unwrap();
+		assert_eq!(options.command, Command::Bench);
+		assert_eq!(options.runs, 25);
+		assert!(parse(&["bench", "--runs", "0"]).is_err());
+		assert!(parse(&["--time"]).unwrap().time);
+	}
+
+#[test]
+	fn submit_needs_day_and_part() {
+		let options = parse(&["submit", "--day", "5", "--part", "2"]).unwrap();
+		assert_eq!(options.command, Command::Submit);
+		assert!(parse(&["submit", "--day", "5"]).is_err());
+		assert!(parse(&["submit", "--part", "1"]).is_err());
+	}
+
+#[test]
+	fn new_day_needs_one_day() {
+		assert_eq!(parse(&["new-day", "7"]).unwrap().days, vec![7]);
+		assert!(parse(&["new-day"]).is_err());
+		assert!(parse(&["new-day", "all"]).is_err());
+	}
+
+#[test]
+	fn render_needs_one_day() {
+		let options = parse(&["render", "3", "--html"]).unwrap();
+		assert_eq!(options.command, Command::Render);
+		assert!(options.html);
+		assert!(parse(&["render"]).is_err());
+		assert!(parse(&["3", "--html"]).is_err());
+	}
+
+#[test]
+	fn verbosity_and_trace() {
+		assert_eq!(parse(&[]).unwrap().verbosity, 0);
+		assert_eq!(parse(&["-v", "--verbose"]).unwrap().verbosity, 2);
+		assert_eq!(parse(&["-vv"]).unwrap().verbosity, 2);
+		let options = parse(&["5", "--trace", "79"]).unwrap();
+		assert_eq!((options.verbosity, options.trace), (1, Some(Interval::new(79, 80))));
+		assert_eq!(parse(&["--trace", "79..93"]).unwrap().trace, Some(Interval::new(79, 93)));
+		assert!(parse(&["--trace", "93..79"]).is_err());
+		assert!(parse(&["--trace", "seed"]).is_err());
+	}
+
+#[test]
+	fn data_root_flag() {
+		assert_eq!(parse(&[]).unwrap().data_root, None);
+		assert_eq!(parse(&["--data", "../aoc-data"]).unwrap().data_root, Some("../aoc-data".to_string()));
+		assert!(parse(&["--data"]).is_err());
+	}
+
+#[test]
+	fn all_inputs_only_when_running() {
+		assert!(parse(&["5", "--all-inputs"]).unwrap().all_inputs);
+		assert!(parse(&["verify", "--all-inputs"]).is_err());
+		assert!(parse(&["5", "--all-inputs", "--input", "foo.txt"]).is_err());
+	}
+
+#[test]
+	fn rejects_garbage() {
+		assert!(parse(&["dayX"]).is_err());
+		assert!(parse(&["--part", "3"]).is_err());
+		assert!(parse(&["--frobnicate"]).is_err());
+		assert!(parse(&["--day"]).is_err());
+	}
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+
+const USER_AGENT: &str = "github.com/thundermoose/Advent-of-Code-2023";
+
+pub struct Client {
+	agent: ureq::Agent,
+	config: Config
+}
+
+impl Client {
+	pub fn new(config: &Config) -> Self {
+		Client {
+			agent: ureq::AgentBuilder::new()
+				.timeout(Duration::from_secs(30))
+				.user_agent(USER_AGENT)
+				.build(),
+			config: config.clone()
+		}
+	}
+
+	pub fn url(&self, path: &str) -> String {
+		format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+	}
+
+	pub fn get(&self, path: &str) -> Result<String> {
+		let url = self.url(path);
+		let response = self.agent.get(&url)
+			.set("Cookie", &format!("session={}", self.config.session()?))
+			.call();
+		read_body(&url, response)
+	}
+
+	pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String> {
+		let url = self.url(path);
+		let response = self.agent.post(&url)
+			.set("Cookie", &format!("session={}", self.config.session()?))
+			.send_form(fields);
+		read_body(&url, response)
+	}
+}
+
+fn read_body(url: &str, response: std::result::Result<ureq::Response, ureq::Error>)
+	-> Result<String> {
+	let http_error = |message: String| Error::Http { url: url.to_string(), message };
+	match response {
+		Ok(response) => response.into_string().map_err(|error| http_error(error.to_string())),
+		Err(ureq::Error::Status(code, response)) =>
+			Err(http_error(format!("status {} {}", code, response.status_text()))),
+		Err(ureq::Error::Transport(transport)) => Err(http_error(
+			match std::error::Error::source(&transport) {
+				Some(source) => format!("{}: {}", transport.kind(), source),
+				None => transport.kind().to_string()
+			}))
+	}
+}
+
+#[cfg(test)]
+pub mod test_server {
+	use std::io::{BufRead, BufReader, Read, Write};
+	use std::net::TcpListener;
+	use std::sync::mpsc::{self, Receiver};
+	use std::thread;
+
+	/// Serves the given (status, body) responses to consecutive connections and
+	/// passes every received request, headers and body, back through the channel.
+	pub fn serve(responses: Vec<(u16, String)>) -> (String, Receiver<String>) {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let base_url = format!("http://{}", listener.local_addr().unwrap());
+		let (sender, receiver) = mpsc::channel();
+		thread::spawn(move || {
+			for (status, body) in responses {
+				let (stream, _) = listener.accept().unwrap();
+				let mut reader = BufReader::new(stream);
+				let mut request = String::new();
+				let mut content_length = 0;
+				loop {
+					let mut line = String::new();
+					reader.read_line(&mut line).unwrap();
+					if let Some((_, length)) = line.to_lowercase().split_once("content-length:") {
+						content_length = length.trim().parse().unwrap();
+					}
+					request.push_str(&line);
+					if line == "\r\n" || line.is_empty() {
+						break;
+					}
+				}
+				let mut request_body = vec![0; content_length];
+				reader.read_exact(&mut request_body).unwrap();
+				request.push_str(&String::from_utf8_lossy(&request_body));
+				sender.send(request).unwrap();
+				let mut stream = reader.into_inner();
+				write!(stream, "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+					   status, body.len(), body).unwrap();
+			}
+		});
+		(base_url, receiver)
+	}
+}
@@ -0,0 +1,108 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::toml_lite::{self, Entry, Value};
+
+pub const CONFIG_FILE: &str = ".aoc.toml";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: usize = 2023;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+	pub session: Option<String>,
+	pub base_url: String,
+	pub data_root: PathBuf
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Config {
+			session: None,
+			base_url: DEFAULT_BASE_URL.to_string(),
+			data_root: Path::new(env!("CARGO_MANIFEST_DIR")).join("problem_data")
+		}
+	}
+}
+
+impl Config {
+	/// Reads `.aoc.toml` from the working directory, or else from the crate root,
+	/// then applies the AOC_* environment variables on top.
+	pub fn load() -> Result<Self> {
+		let mut config = Config::default();
+		let crate_config = Path::new(env!("CARGO_MANIFEST_DIR")).join(CONFIG_FILE);
+		let config_file = [Path::new(CONFIG_FILE), crate_config.as_path()].into_iter()
+			.find(|path| path.is_file());
+		if let Some(config_file) = config_file {
+			let filename = config_file.to_string_lossy();
+			config.apply_entries(&filename, toml_lite::read(&filename)?)?;
+		}
+		if let Ok(session) = env::var("AOC_SESSION") {
+			config.session = Some(session);
+		}
+		if let Ok(base_url) = env::var("AOC_BASE_URL") {
+			config.base_url = base_url;
+		}
+		if let Ok(data_root) = env::var("AOC_DATA_ROOT") {
+			config.data_root = PathBuf::from(data_root);
+		}
+		Ok(config)
+	}
+
+	fn apply_entries(&mut self, filename: &str, entries: Vec<Entry>) -> Result<()> {
+		for entry in entries {
+			match (entry.key.as_str(), entry.value) {
+				("session", Value::Text(session)) => self.session = Some(session),
+				("base_url", Value::Text(base_url)) => self.base_url = base_url,
+				("data_root", Value::Text(data_root)) => self.data_root = Path::new(filename)
+					.parent()
+					.unwrap_or(Path::new(""))
+					.join(data_root),
+				(key, _) => return Err(Error::parse_at_column(entry.column, key, "Unknown setting or wrong type")
+									   .at(filename, entry.line))
+			}
+		}
+		Ok(())
+	}
+
+	pub fn session(&self) -> Result<&str> {
+		self.session.as_deref().ok_or_else(|| Error::Config(format!(
+			"no session token, set AOC_SESSION or session in {}", CONFIG_FILE)))
+	}
+}
+
+#[cfg(test)]
+mod config_tests {
+	use super::*;
+
+#[test]
+	fn entries_override_defaults() {
+		let mut config = Config::default();
+		let entries = toml_lite::parse("c.toml", "session = \"abc\"\nbase_url = \"http://x\"")
+			.unwrap();
+		config.apply_entries("c.toml", entries).unwrap();
+		assert_eq!(config.session().unwrap(), "abc");
+		assert_eq!(config.base_url, "http://x");
+	}
+
+#[test]
+	fn data_root_is_relative_to_the_config_file() {
+		assert!(Config::default().data_root.ends_with("problem_data"));
+		assert!(Config::default().data_root.is_absolute());
+		let mut config = Config::default();
+		let entries = toml_lite::parse("team/.aoc.toml", "data_root = \"../aoc-data\"").unwrap();
+		config.apply_entries("team/.aoc.toml", entries).unwrap();
+		assert_eq!(config.data_root, Path::new("team/../aoc-data"));
+		let entries = toml_lite::parse(".aoc.toml", "data_root = \"/srv/aoc\"").unwrap();
+		config.apply_entries(".aoc.toml", entries).unwrap();
+		assert_eq!(config.data_root, Path::new("/srv/aoc"));
+	}
+
+#[test]
+	fn unknown_settings_and_missing_session() {
+		let mut config = Config::default();
+		let entries = toml_lite::parse("c.toml", "sesion = \"abc\"").unwrap();
+		assert!(config.apply_entries("c.toml", entries).is_err());
+		assert!(matches!(config.session(), Err(Error::Config(_))));
+	}
+}
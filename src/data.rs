@@ -0,0 +1,129 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::solution::Runnable;
+
+#[derive(Debug, PartialEq)]
+pub struct MissingFile {
+	pub day: usize,
+	pub kind: &'static str,
+	pub filename: String
+}
+
+impl fmt::Display for MissingFile {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.kind {
+			"input" => write!(f, "Day {} input {} is missing, run `fetch {}` to download it",
+							  self.day, self.filename, self.day),
+			kind => write!(f, "Day {} {} {} is missing, copy it from the puzzle description",
+						   self.day, kind, self.filename)
+		}
+	}
+}
+
+pub fn missing_files(solution: &dyn Runnable, data_root: &Path, parts: &[usize]) -> Vec<MissingFile> {
+	let mut files: Vec<(&'static str, String)> = parts.iter()
+		.map(|part| ("example", solution.example_file(data_root, *part)))
+		.collect();
+	files.push(("input", solution.input_file(data_root)));
+	files.dedup();
+	files.into_iter()
+		.filter(|(_, filename)| !Path::new(filename).is_file())
+		.map(|(kind, filename)| MissingFile { day: solution.day(), kind, filename })
+		.collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NamedInput {
+	pub name: String,
+	pub filename: String
+}
+
+/// Every `inputs/NAME.txt` of a day, named `inputs.NAME` like their section in answers.toml.
+pub fn named_inputs(solution: &dyn Runnable, data_root: &Path) -> Result<Vec<NamedInput>> {
+	let directory = solution.inputs_dir(data_root);
+	if !Path::new(&directory).is_dir() {
+		return Ok(Vec::new());
+	}
+	let entries = fs::read_dir(&directory).map_err(|error| Error::io(&directory, error))?;
+	let mut inputs = Vec::new();
+	for entry in entries {
+		let path = entry.map_err(|error| Error::io(&directory, error))?.path();
+		if let (Some(stem), Some("txt")) = (path.file_stem(), path.extension().and_then(|e| e.to_str())) {
+			inputs.push(NamedInput {
+				name: format!("inputs.{}", stem.to_string_lossy()),
+				filename: path.to_string_lossy().to_string()
+			});
+		}
+	}
+	inputs.sort_by(|a, b| a.name.cmp(&b.name));
+	Ok(inputs)
+}
+
+#[cfg(test)]
+mod data_tests {
+	use super::*;
+	use crate::day1::Day1;
+	use crate::error::Result;
+	use crate::input::Source;
+	use crate::solution::Solution;
+	use crate::test_support::TempDir;
+
+	struct Unpublished;
+
+	impl Solution for Unpublished {
+		const DAY: usize = 26;
+		type Input = ();
+		type Answer1 = usize;
+		type Answer2 = usize;
+
+		fn parse(_source: &Source) -> Result<()> {
+			Ok(())
+		}
+
+		fn part1(_input: &()) -> usize {
+			0
+		}
+
+		fn part2(_input: &()) -> usize {
+			0
+		}
+	}
+
+#[test]
+	fn complete_days_have_nothing_missing() {
+		assert_eq!(missing_files(&Day1, Path::new("problem_data"), &[1, 2]), Vec::new());
+	}
+
+#[test]
+	fn reports_each_missing_file_once() {
+		let missing = missing_files(&Unpublished, Path::new("problem_data"), &[1, 2]);
+		assert_eq!(missing, vec![
+			MissingFile { day: 26, kind: "example", filename: "problem_data/day26/example.txt".to_string() },
+			MissingFile { day: 26, kind: "input", filename: "problem_data/day26/input.txt".to_string() }
+		]);
+		assert_eq!(missing[0].to_string(), "Day 26 example problem_data/day26/example.txt is missing, \
+											copy it from the puzzle description");
+		assert_eq!(missing[1].to_string(),
+				   "Day 26 input problem_data/day26/input.txt is missing, run `fetch 26` to download it");
+	}
+
+#[test]
+	fn named_inputs_are_sorted_text_files() {
+		let directory = TempDir::new("data");
+		let data_root = directory.path();
+		assert_eq!(named_inputs(&Unpublished, data_root).unwrap(), Vec::new());
+		let inputs = data_root.join("day26/inputs");
+		fs::create_dir_all(&inputs).unwrap();
+		for name in ["bob.txt", "alice.txt", "notes.md"] {
+			fs::write(inputs.join(name), "").unwrap();
+		}
+		let names: Vec<String> = named_inputs(&Unpublished, data_root).unwrap()
+			.into_iter()
+			.map(|input| input.name)
+			.collect();
+		assert_eq!(names, vec!["inputs.alice", "inputs.bob"]);
+	}
+}
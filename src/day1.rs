@@ -1,49 +1,47 @@
-use std::fs::File;
-use std::io::BufReader;
-use std::io::BufRead;
 use regex::Regex;
 
-const EXAMPLE_DATA_DAY1_PART1: &str = "problem_data/day1/example_part1.txt";
-const EXAMPLE_DATA_DAY1_PART2: &str = "problem_data/day1/example_part2.txt";
-const INPUT_DAY1_PART: &str = 		  "problem_data/day1/input.txt";
+use crate::error::Result;
+use crate::input::Source;
+use crate::solution::Solution;
 
 const MATCH_DIGIT: &str = "(\\d)|(one)|(two)|(three)|(four)|(five)|(six)|(seven)|(eight)|(nine)|(zero)";
 const MATCH_TIGID: &str = "(\\d)|(eno)|(owt)|(eerht)|(ruof)|(evif)|(xis)|(neves)|(thgie)|(enin)|(orez)";
 
-pub fn run_day1() {
-	println!("Day 1:");
-	part1(EXAMPLE_DATA_DAY1_PART1);
-	part1(INPUT_DAY1_PART);
-	part2(EXAMPLE_DATA_DAY1_PART2);
-	part2(INPUT_DAY1_PART);
-}
+pub struct Day1;
 
-fn part1(filename: &str) {
-	println!("Part1: Reading: {}", filename);
-	let input_file = File::open(filename).unwrap();	
-	let input_buffer = BufReader::new(input_file);
-	println!("Calibration: {}",
-			 input_buffer.lines()
-			 .map(|row| get_calibration_contribution(row.unwrap().as_str()))
-			 .sum::<u64>());
-}
+impl Solution for Day1 {
+	const DAY: usize = 1;
+	type Input = Vec<String>;
+	type Answer1 = u64;
+	type Answer2 = u64;
 
-fn part2(filename: &str) {
-	println!("Part2: Reading: {}", filename);
-	let input_file = File::open(filename).unwrap();	
-	let input_buffer = BufReader::new(input_file);
-	println!("Calibration: {}",
-			 input_buffer.lines()
-			 .map(|row| get_calibration_contribution_regex(row.unwrap().as_str()))
-			 .sum::<u64>());
+	fn parse(source: &Source) -> Result<Self::Input> {
+		Ok(source.lines().to_vec())
+	}
+
+	fn part1(rows: &Self::Input) -> u64 {
+		rows.iter()
+			.map(|row| get_calibration_contribution(row))
+			.sum()
+	}
+
+	fn part2(rows: &Self::Input) -> u64 {
+		rows.iter()
+			.map(|row| get_calibration_contribution_regex(row))
+			.sum()
+	}
+
+	fn example_file(part: usize) -> String {
+		format!("day1/example_part{}.txt", part)
+	}
 }
 
 fn get_calibration_contribution(row: &str) -> u64 {
-	combine_digits(first_digit(&row), last_digit(&row)) as u64
+	combine_digits(first_digit(row), last_digit(row)) as u64
 }
 
 fn get_calibration_contribution_regex(row: &str) -> u64 {
-	combine_digits(first_regex_digit(&row), last_regex_digit(&row)) as u64
+	combine_digits(first_regex_digit(row), last_regex_digit(row)) as u64
 }
 
 fn first_digit(row: &str) -> u8 {
@@ -52,7 +50,7 @@ fn first_digit(row: &str) -> u8 {
 			return d.to_digit(10).unwrap() as u8;
 		}
 	}
-	return 0;
+	0
 }
 
 fn last_digit(row: &str) -> u8 {
@@ -61,7 +59,7 @@ fn last_digit(row: &str) -> u8 {
 			return d.to_digit(10).unwrap() as u8;
 		}
 	}
-	return 0;
+	0
 }
 
 fn first_regex_digit(row: &str) -> u8 {
@@ -1,8 +1,7 @@
-use std::fs::File;
-use std::io::{BufReader, BufRead};
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::solution::Solution;
 
-const EXAMPLE_DATA_DAY2: &str = "problem_data/day2/example_part1.txt";
-const INPUT_DAY2: &str = "problem_data/day2/input.txt";
 const MAX_NUM_RED: usize = 12;
 const MAX_NUM_GREEN: usize = 13;
 const MAX_NUM_BLUE: usize = 14;
@@ -14,32 +13,34 @@ struct CubeSet {
 }
 
 impl CubeSet {
-	fn new(description: &str) -> Option<Self> {
+	fn new(line: &str, description: &str) -> Result<Self> {
 		let mut count_blue: usize = 0;
 		let mut count_red: usize = 0;
 		let mut count_green: usize = 0;
 		for element in description.split(',') {
-			let (count, color) = element.trim().split_once(' ')?;
+			let (count_str, color) = element.trim().split_once(' ')
+				.ok_or_else(|| Error::parse(line, element, "Expected <count> <color>"))?;
+			let count = count_str.parse::<usize>()
+				.map_err(|_| Error::parse(line, count_str, "Not a count"))?;
 			match color {
-				"blue" => count_blue = count_blue + count.parse::<usize>().unwrap(),
-				"red" => count_red = count_red + count.parse::<usize>().unwrap(),
-				"green" => count_green = count_green + count.parse::<usize>().unwrap(),
-				_ => {}
-
+				"blue" => count_blue += count,
+				"red" => count_red += count,
+				"green" => count_green += count,
+				_ => return Err(Error::parse(line, color, "Unknown color"))
 			};
 		}
-		Some(CubeSet {
+		Ok(CubeSet {
 			number_blue: count_blue,
 			number_red: count_red,
 			number_green: count_green,
 		})
 	}
 
-	fn parse_cube_sets(description: &str) -> Option<Vec<Self>> {
+	fn parse_cube_sets(line: &str, description: &str) -> Result<Vec<Self>> {
 		description
 			.split(';')
-			.map(|sub_description| Self::new(sub_description))
-			.collect::<Option<Vec<Self>>>()
+			.map(|sub_description| Self::new(line, sub_description))
+			.collect::<Result<Vec<Self>>>()
 	}
 
 	fn is_possible(&self, 
@@ -56,18 +57,21 @@ impl CubeSet {
 	}
 }
 
-struct Game {
+pub struct Game {
 	game_number: usize,
 	cube_sets: Vec<CubeSet>
 }
 
 impl Game {
-	fn new(description: &str) -> Option<Game> {
-		let (head, tail) = description.split_once(':')?;
-		let (_, number) = head.split_once(' ')?;	
-		let current_game_number = number.parse::<usize>().ok()?;
-		let current_cube_sets = CubeSet::parse_cube_sets(tail)?;
-		Some(Game {
+	fn new(description: &str) -> Result<Game> {
+		let (head, tail) = description.split_once(':')
+			.ok_or_else(|| Error::parse(description, description, "Expected Game <number>:"))?;
+		let (_, number) = head.split_once(' ')
+			.ok_or_else(|| Error::parse(description, head, "Expected Game <number>"))?;	
+		let current_game_number = number.parse::<usize>()
+			.map_err(|_| Error::parse(description, number, "Not a game number"))?;
+		let current_cube_sets = CubeSet::parse_cube_sets(description, tail)?;
+		Ok(Game {
 			game_number: current_game_number,
 			cube_sets: current_cube_sets
 			 })
@@ -90,42 +94,53 @@ impl Game {
 	}
 }
 
-pub fn run_day2() {
-	println!("Day 2:");
-	part1(EXAMPLE_DATA_DAY2);
-	part1(INPUT_DAY2);
-	part2(EXAMPLE_DATA_DAY2);
-	part2(INPUT_DAY2);
-}
+pub struct Day2;
 
-fn part1(filename: &str) {
-	println!("Part1: {}", filename);
-	let games = read_games(filename).unwrap();	
-	let id_sum = games
-		.iter()
-		.map(|game| {
-			 if game.is_possible(MAX_NUM_BLUE, MAX_NUM_RED, MAX_NUM_GREEN) { 
-			 game.game_number 
-			 } else {
-			 0 as usize
-			 }
-			 }).sum::<usize>();
-	println!("ID sum: {}", id_sum);
-}
+impl Solution for Day2 {
+	const DAY: usize = 2;
+	type Input = Vec<Game>;
+	type Answer1 = usize;
+	type Answer2 = usize;
+
+	fn parse(source: &Source) -> Result<Self::Input> {
+		source.parse_lines(Game::new)
+	}
+
+	fn part1(games: &Self::Input) -> usize {
+		games
+			.iter()
+			.filter(|game| game.is_possible(MAX_NUM_BLUE, MAX_NUM_RED, MAX_NUM_GREEN))
+			.map(|game| game.game_number)
+			.sum()
+	}
 
-fn part2(filename: &str) {
-	println!("Part2: {}", filename);
-	let games = read_games(filename).unwrap();	
-	let total_power: usize = 
-		games.iter().map(|game| game.minimum_cube_set().power()).sum();
-	println!("Total power: {}", total_power);
+	fn part2(games: &Self::Input) -> usize {
+		games.iter().map(|game| game.minimum_cube_set().power()).sum()
+	}
 }
 
-fn read_games(filename: &str) -> Option<Vec<Game>> {
-	let input_file = File::open(filename).ok()?;	
-	let input_buffer = BufReader::new(input_file);
-	input_buffer
-		.lines()
-		.map(|line| Game::new(line.unwrap().as_str()))
-		.collect::<Option<Vec<Game>>>()
+#[cfg(test)]
+mod day2_tests {
+	use super::*;
+
+	const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+#[test]
+	fn example_answers() {
+		let games = Day2::parse(&Source::from_text("example", EXAMPLE)).unwrap();
+		assert_eq!(Day2::part1(&games), 8);
+		assert_eq!(Day2::part2(&games), 2286);
+	}
+
+#[test]
+	fn unknown_color_is_located() {
+		let source = Source::from_text("example", "Game 1: 3 blue\nGame 2: 4 purple");
+		assert_eq!(Day2::parse(&source).err().unwrap().to_string(),
+				   "example:2:11: Unknown color: \"purple\"");
+	}
 }
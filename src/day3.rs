@@ -1,189 +1,334 @@
-use std::fs::File;
-use std::io::{BufReader, BufRead};
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
 
-const EXAMPLE_DATA_PART1: &str = "problem_data/day3/example_part1.txt";
-const INPUT_DATA: &str = "problem_data/day3/input.txt";
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::input::Source;
+use crate::render::{self, Colour, Format};
+use crate::solution::Solution;
 
-struct EngineSchematic {
-cells: Vec<String>,
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct NumberSpan {
+	row: usize,
+	start: usize,
+	end: usize,
+	value: u64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Symbol {
+	row: usize,
+	column: usize,
+	character: char
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregate {
+	Product,
+	Sum
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolRule {
+	pub characters: Option<String>,
+	pub neighbours: RangeInclusive<usize>,
+	pub aggregate: Aggregate
+}
+
+impl SymbolRule {
+	pub fn gear() -> Self {
+		SymbolRule {
+			characters: Some("*".to_string()),
+			neighbours: 2..=2,
+			aggregate: Aggregate::Product
+		}
+	}
+
+	pub fn any() -> Self {
+		SymbolRule {
+			characters: None,
+			neighbours: 0..=usize::MAX,
+			aggregate: Aggregate::Sum
+		}
+	}
+
+	fn matches(&self, character: char, neighbours: usize) -> bool {
+		self.characters.as_ref().is_none_or(|characters| characters.contains(character)) &&
+			self.neighbours.contains(&neighbours)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolMatch {
+	pub row: usize,
+	pub column: usize,
+	pub character: char,
+	pub numbers: Vec<u64>,
+	pub value: u64
+}
+
+pub struct EngineSchematic {
+	cells: Grid<char>,
+	numbers: Vec<NumberSpan>,
+	symbols: Vec<Symbol>,
+	span_index: Grid<Option<usize>>
 }
 
 impl EngineSchematic {
-	fn read(filename: &str) -> Self {
-		let input_file = File::open(filename).unwrap();	
-		let input_buffer = BufReader::new(input_file);
-		EngineSchematic {
-cells: input_buffer
-		   .lines()
-		   .map(|line| line.unwrap().to_string())
-		   .collect()
+	fn read(source: &Source) -> Result<Self> {
+		Ok(Self::tokenize(Grid::parse(source, |cell| cell)?))
+	}
+
+	fn tokenize(cells: Grid<char>) -> Self {
+		let mut numbers: Vec<NumberSpan> = Vec::new();
+		let mut symbols: Vec<Symbol> = Vec::new();
+		let mut span_index: Grid<Option<usize>> =
+			Grid::new(cells.width(), cells.height(), vec![None; cells.width() * cells.height()])
+			.unwrap();
+		for (row, column) in cells.positions() {
+			let cell = cells[(row, column)];
+			match cell.to_digit(10) {
+				Some(digit) => {
+					if column == 0 || span_index[(row, column - 1)].is_none() {
+						numbers.push(NumberSpan { row, start: column, end: column, value: 0 });
+					}
+					let span = numbers.last_mut().unwrap();
+					span.end = column + 1;
+					span.value = span.value * 10 + digit as u64;
+					span_index[(row, column)] = Some(numbers.len() - 1);
+				},
+				None if cell != '.' => symbols.push(Symbol { row, column, character: cell }),
+				None => {}
+			}
 		}
+		EngineSchematic { cells, numbers, symbols, span_index }
 	}
 
-	fn cell_indices(&self) -> Vec<(usize, usize)> {
-		(0..self.get_number_of_rows())
-		.flat_map(|row| (0..self.get_number_of_columns())
-				  .map(move |column| (row, column)))
-		.collect()
+	fn span_at(&self, row: usize, column: usize) -> Option<&NumberSpan> {
+		self.span_index.get(row, column)?.map(|index| &self.numbers[index])
 	}
 
-	fn get_number_of_rows(&self) -> usize {
-		return self.cells.len();
+	fn is_symbol(&self, row: usize, column: usize) -> bool {
+		self.cells.get(row, column)
+			.is_some_and(|cell| !cell.is_ascii_digit() && *cell != '.')
 	}
 
-	fn get_number_of_columns(&self) -> usize {
-		if self.cells.len() == 0 {0} else {self.cells[0].len()}
+	fn is_part_number(&self, span: &NumberSpan) -> bool {
+		(span.start..span.end)
+			.flat_map(|column| self.cells.neighbours8(span.row, column))
+			.any(|(row, column)| self.is_symbol(row, column))
 	}
 
-	fn get_cell(&self, row: usize, column: usize) -> char {
-		self.cells[row].chars().nth(column).unwrap()
+	fn adjacent_numbers(&self, symbol: &Symbol) -> Vec<&NumberSpan> {
+		let mut spans: Vec<&NumberSpan> = self.cells
+			.neighbours8(symbol.row, symbol.column)
+			.filter_map(|(row, column)| self.span_at(row, column))
+			.collect();
+		spans.sort_by_key(|span| (span.row, span.start));
+		spans.dedup();
+		spans
 	}
 
-	fn is_cell_number(&self, row: usize, column: usize) -> bool {
-		self.get_cell(row, column).is_numeric()
-	}	
+	pub fn find_symbols(&self, rule: &SymbolRule) -> Vec<SymbolMatch> {
+		self.symbols
+			.iter()
+			.map(|symbol| (symbol, self.adjacent_numbers(symbol)))
+			.filter(|(symbol, spans)| rule.matches(symbol.character, spans.len()))
+			.map(|(symbol, spans)| {
+				let numbers: Vec<u64> = spans.iter().map(|span| span.value).collect();
+				let value = match rule.aggregate {
+					Aggregate::Product => numbers.iter().product(),
+					Aggregate::Sum => numbers.iter().sum()
+				};
+				SymbolMatch {
+					row: symbol.row,
+					column: symbol.column,
+					character: symbol.character,
+					numbers,
+					value
+				}
+			})
+			.collect()
+	}
 
-	fn is_cell_gear(&self, row: usize, column: usize) -> bool {
-		if self.get_cell(row, column) == '*' {
-			self.get_number_neighbouring_numbers(row, column) == 2
-		} else {
-			false
+	pub fn symbols_by_character(&self, rule: &SymbolRule) -> BTreeMap<char, Vec<SymbolMatch>> {
+		let mut groups: BTreeMap<char, Vec<SymbolMatch>> = BTreeMap::new();
+		for symbol_match in self.find_symbols(rule) {
+			groups.entry(symbol_match.character).or_default().push(symbol_match);
 		}
+		groups
 	}
 
-	fn is_number_start(&self, row: usize, column: usize) -> bool {
-		if self.is_cell_number(row,column) {
-			if self.with_in_bounds(row as i64, column as i64 - 1) {
-				!self.is_cell_number(row, column - 1)
-			} else {
-				true
+	fn render(&self, format: Format) -> String {
+		let gears = self.find_symbols(&SymbolRule::gear());
+		let mut output = String::new();
+		for row in 0..self.cells.height() {
+			let mut column = 0;
+			while column < self.cells.width() {
+				let cell = self.cells[(row, column)];
+				if let Some(span) = self.span_at(row, column) {
+					let colour = if self.is_part_number(span) { Colour::Green } else { Colour::Red };
+					output += &render::paint(format, colour, &span.value.to_string(), None);
+					column = span.end;
+					continue;
+				}
+				let text = cell.to_string();
+				output += &match gears.iter().find(|gear| (gear.row, gear.column) == (row, column)) {
+					Some(gear) => render::paint(format, Colour::Yellow, &text,
+												Some(&gear.value.to_string())),
+					None if cell == '.' => render::paint(format, Colour::Grey, &text, None),
+					None => render::paint(format, Colour::Blue, &text, None)
+				};
+				column += 1;
 			}
-		} else {
-			false
+			output.push('\n');
+		}
+		output.push('\n');
+		for (character, matches) in self.symbols_by_character(&SymbolRule::any()) {
+			let total: u64 = matches.iter().map(|symbol_match| symbol_match.value).sum();
+			output += &render::escape(format, &format!("Symbol {}: {} found, adjacent numbers sum to {}\n",
+													   character, matches.len(), total));
 		}
+		for gear in &gears {
+			let factors: Vec<String> = gear.numbers.iter().map(|number| number.to_string()).collect();
+			output += &render::escape(format, &format!("Gear at row {}, column {}: {} = {}\n",
+													   gear.row + 1, gear.column + 1,
+													   factors.join(" * "), gear.value));
+		}
+		render::document(format, "Day 3 engine schematic", &output)
 	}
+}
+
+pub struct Day3;
 
-	fn with_in_bounds(&self, row: i64, column: i64) -> bool {
-		row >= 0 && row < self.get_number_of_rows() as i64 
-			&& column >= 0 && column < self.get_number_of_columns() as i64
+impl Solution for Day3 {
+	const DAY: usize = 3;
+	type Input = EngineSchematic;
+	type Answer1 = u64;
+	type Answer2 = u64;
+
+	fn parse(source: &Source) -> Result<Self::Input> {
+		EngineSchematic::read(source)
 	}
 
-	fn get_neigbour_indicies(&self, row: usize, column: usize) -> Vec<(usize, usize)> {
-		(0..3).flat_map(|row| (0..3).map(move |column| (row, column)))
-			.map(|(i, j)|((i + row) as i64 - 1, (j + column) as i64 - 1))
-			.filter(|(i, j)| {
-					!(*i == row as i64 && *j == column as i64)
-					})
-			.filter(|(i, j)| self.with_in_bounds(*i,*j))	
-			.map(|(i, j)| {
-				 (i as usize, j as usize)
-				 })
-			.collect()
+	fn part1(engine_schematic: &Self::Input) -> u64 {
+		engine_schematic
+			.numbers
+			.iter()
+			.filter(|span| engine_schematic.is_part_number(span))
+			.map(|span| span.value)
+			.sum()
 	}
 
-	fn is_neighbours_symbol(&self, row: usize, column: usize) -> bool {
-		self.get_neigbour_indicies(row, column)
+	fn part2(engine_schematic: &Self::Input) -> u64 {
+		engine_schematic
+			.find_symbols(&SymbolRule::gear())
 			.iter()
-			.any(|(i, j)| {
-				 (!self.is_cell_number(*i,*j)) &&
-				 (self.get_cell(*i,*j) != '.')
-				 })
+			.map(|gear| gear.value)
+			.sum()
 	}
 
-	fn begining_of_number(&self, row: usize, column: usize) -> usize {
-		(1..=column).rev().find(|c| !self.is_cell_number(row, *c - 1))
-			.unwrap_or(0)
+	fn render(engine_schematic: &Self::Input, format: Format) -> Option<String> {
+		Some(engine_schematic.render(format))
 	}
+}
+
+#[cfg(test)]
+mod day3_tests {
+	use super::*;
 
-	fn end_of_number(&self, row: usize, column: usize) -> usize {
-		(column+1..self.get_number_of_columns())
-			.find(|c| !self.is_cell_number(row, *c))
-			.unwrap_or(self.get_number_of_columns())
+	const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+	fn schematic(text: &str) -> EngineSchematic {
+		EngineSchematic::read(&Source::from_text("schematic", text)).unwrap()
 	}
 
-	fn is_part_number_start(&self, row: usize, column: usize) -> bool {
-		if !self.is_number_start(row,column) {
-			false
-		} else {
-			(column..self.end_of_number(row, column))
-				.any(|c| self.is_neighbours_symbol(row, c))
-		}
+#[test]
+	fn example_answers() {
+		let engine_schematic = schematic(EXAMPLE);
+		assert_eq!(Day3::part1(&engine_schematic), 4361);
+		assert_eq!(Day3::part2(&engine_schematic), 467835);
 	}
 
-	fn get_number_neighbouring_numbers(&self, row: usize, column: usize) -> usize {
-		let mut previous_row: usize = usize::MAX;
-		let mut previous_column: usize = usize::MAX;
-		self
-			.get_neigbour_indicies(row, column)
-			.iter()
-			.filter(|(row, column)| self.is_cell_number(*row, *column))
-			.filter(|(row, column)| {
-						let result = !(*row == previous_row && *column == previous_column+1);
-						previous_row = *row;
-						previous_column = *column;
-						result
-					})
-			.count()
-	}
-
-	fn get_number_starting_at(&self, row: usize, column: usize) -> u32 {
-		let mut accumulator: u32 = 0;
-		for c in column..self.end_of_number(row, column) {
-			accumulator = accumulator*10 + self.get_cell(row,c).to_digit(10).unwrap();
-		}
-		accumulator
+#[test]
+	fn tokenize_spans_and_symbols() {
+		let engine_schematic = schematic("467..114\n...*....\n..35.#..");
+		assert_eq!(engine_schematic.numbers, vec![
+			NumberSpan { row: 0, start: 0, end: 3, value: 467 },
+			NumberSpan { row: 0, start: 5, end: 8, value: 114 },
+			NumberSpan { row: 2, start: 2, end: 4, value: 35 }
+		]);
+		assert_eq!(engine_schematic.symbols, vec![
+			Symbol { row: 1, column: 3, character: '*' },
+			Symbol { row: 2, column: 5, character: '#' }
+		]);
+		assert_eq!(engine_schematic.span_at(0, 6).map(|span| span.value), Some(114));
+		assert_eq!(engine_schematic.span_at(1, 0), None);
 	}
 
-	fn get_number(&self, row: usize, column: usize) -> u32 {
-		self.get_number_starting_at(row,self.begining_of_number(row,column))
+#[test]
+	fn numbers_at_row_ends_stay_separate() {
+		let engine_schematic = schematic("..12\n34*.\n....");
+		assert_eq!(engine_schematic.numbers.len(), 2);
+		assert_eq!(Day3::part1(&engine_schematic), 46);
+		assert_eq!(Day3::part2(&engine_schematic), 408);
 	}
 
-	fn gear_ratio(&self, row: usize, column: usize) -> u64 {
-		let mut previous_row: usize = usize::MAX;
-		let mut previous_column: usize = usize::MAX;
-		self
-			.get_neigbour_indicies(row, column)
-			.iter()
-			.filter(|(row, column)| self.is_cell_number(*row, *column))
-			.filter(|(row, column)| {
-						let result = !(*row == previous_row && *column == previous_column+1);
-						previous_row = *row;
-						previous_column = *column;
-						result
-					})
-			.map(|(row, column)| self.get_number(*row, *column) as u64)
-			.product()
+#[test]
+	fn gears_count_each_number_once() {
+		assert_eq!(Day3::part2(&schematic("123.\n.*..\n999.")), 123 * 999);
+		assert_eq!(Day3::part2(&schematic("1.1\n.*.\n1..")), 0);
 	}
-}
 
-pub fn run_day3() {
-	println!("Day 3");
-	part1(EXAMPLE_DATA_PART1);
-	part1(INPUT_DATA);
-	part2(EXAMPLE_DATA_PART1);
-	part2(INPUT_DATA);
-}
+#[test]
+	fn symbols_grouped_by_character() {
+		let engine_schematic = schematic(EXAMPLE);
+		let groups = engine_schematic.symbols_by_character(&SymbolRule::any());
+		assert_eq!(groups.keys().collect::<String>(), "#$*+");
+		assert_eq!(groups[&'*'].len(), 3);
+		assert_eq!(groups[&'#'][0].numbers, vec![633]);
+		assert_eq!(groups[&'*'][0].value, 467 + 35);
+	}
 
-fn part1(filename: &str) {
-	println!("Part 1: {}", filename);
-	let engine_schematic = EngineSchematic::read(filename);
-	let sum_of_partnumbers: u32 = engine_schematic
-		.cell_indices()
-		.iter()
-		.filter(|(row, column)| engine_schematic.is_part_number_start(*row, *column))
-		.map(|(row, column)| engine_schematic.get_number_starting_at(*row, *column))
-		.sum();
-	println!("sum_of_partnumbers: {}", sum_of_partnumbers);
-}
+#[test]
+	fn configurable_rules() {
+		let engine_schematic = schematic(EXAMPLE);
+		let lonely_stars = SymbolRule { neighbours: 1..=1, ..SymbolRule::gear() };
+		let found = engine_schematic.find_symbols(&lonely_stars);
+		assert_eq!(found.len(), 1);
+		assert_eq!((found[0].row, found[0].column, found[0].value), (4, 3, 617));
+		let sums = SymbolRule {
+			characters: Some("#$".to_string()),
+			neighbours: 1..=8,
+			aggregate: Aggregate::Sum
+		};
+		let values: Vec<u64> = engine_schematic.find_symbols(&sums).iter().map(|m| m.value).collect();
+		assert_eq!(values, vec![633, 664]);
+	}
 
-fn part2(filename: &str) {
-	println!("Part 2: {}", filename);
-	let engine_schematic = EngineSchematic::read(filename);
-	let sum_of_gear_ratios: u64 = engine_schematic
-		.cell_indices()
-		.iter()
-		.filter(|(row, column)| engine_schematic.is_cell_gear(*row, *column))
-		.map(|(row, column)| engine_schematic.gear_ratio(*row, *column))
-		.sum();
-	println!("sum_of_gear_ratios: {}", sum_of_gear_ratios);
+#[test]
+	fn render_marks_parts_and_gears() {
+		let engine_schematic = schematic("467..114\n...*....\n..35....");
+		let ansi = engine_schematic.render(Format::Ansi);
+		assert!(ansi.starts_with("\x1b[32m467\x1b[0m\x1b[90m.\x1b[0m\x1b[90m.\x1b[0m\x1b[31m114\x1b[0m"));
+		assert!(ansi.contains("\x1b[33m*\x1b[0m"));
+		assert!(ansi.ends_with("Symbol *: 1 found, adjacent numbers sum to 502\n\
+								Gear at row 2, column 4: 467 * 35 = 16345\n"));
+		let engine_schematic = schematic("12.\n...\n.3#");
+		let html = engine_schematic.render(Format::Html);
+		assert!(html.contains("<span class=\"red\">12</span>"));
+		assert!(html.contains("<span class=\"green\">3</span><span class=\"blue\">#</span>"));
+		assert!(html.contains("Symbol #: 1 found, adjacent numbers sum to 3\n"));
+		assert!(!html.contains("Gear at"));
+	}
 }
@@ -1,9 +1,8 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::collections::{HashSet, HashMap};
 
-const EXAMPLE_DATA_PART1: &str = "problem_data/day4/example_part1.txt";
-const INPUT: &str = "problem_data/day4/input.txt";
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::solution::Solution;
 
 #[derive(Debug)]
 struct CopiesRecord {
@@ -29,8 +28,8 @@ impl CopiesRecord {
 			.map(|k| k + card_number)	
 			.for_each(|k| {
 					  let increament = number_current_copies 
-					  	+ number_of_coppies.get(&k).map(|x| *x).unwrap_or(1 as usize);
-					  number_of_coppies.insert(k as usize, increament);
+					  	+ number_of_coppies.get(&k).copied().unwrap_or(1);
+					  number_of_coppies.insert(k, increament);
 					  });
 		CopiesRecord {
 			current_number_of_copies: number_of_coppies,
@@ -40,38 +39,37 @@ impl CopiesRecord {
 }
 
 #[derive(Debug)]
-struct ScratchCard {
+pub struct ScratchCard {
 	card_number: usize, 
 	winning_numbers: HashSet<u64>,	
 	lottery_numbers: Vec<u64>
 }
 
 impl ScratchCard {
-	fn parse(card_string: &str) -> Result<Self, String> {
-		let (head, body) = card_string.split_once(':').ok_or("Could not split on :".to_string())?;
-		let (card, card_number_string) = head.split_once(' ').ok_or("Could not split head".to_string())?;
+	fn parse(card_string: &str) -> Result<Self> {
+		let error = |text: &str, message: &str| Error::parse(card_string, text, message);
+		let (head, body) = card_string.split_once(':')
+			.ok_or_else(|| error(card_string, "Could not split on :"))?;
+		let (card, card_number_string) = head.split_once(' ')
+			.ok_or_else(|| error(head, "Could not split head"))?;
 		let (winning_numbers_string, lottery_numbers_string) =
-			body.split_once('|').ok_or("Could not split body")?;
+			body.split_once('|').ok_or_else(|| error(body, "Could not split body"))?;
+		let parse_numbers = |numbers_string: &str| numbers_string
+			.split(' ')
+			.filter(|w| !w.is_empty())
+			.map(|w| w.parse::<u64>().map_err(|_| error(w, "Could not parse number")))
+			.collect::<Result<Vec<u64>>>();
 		if card == "Card" {
 			Ok(ScratchCard {
 					card_number: card_number_string.trim()
 					.parse::<usize>()
-					.map_err(|_| "Could not parse card number".to_string())?,
+					.map_err(|_| error(card_number_string.trim(), "Could not parse card number"))?,
 					winning_numbers: 
-						HashSet::from_iter(winning_numbers_string
-										   .trim()
-										   .split(' ')
-										   .filter(|w| w.len()>0)
-										   .map(|w| w.parse::<u64>().unwrap())),
-					lottery_numbers: lottery_numbers_string
-									.trim()
-									.split(' ')
-								    .filter(|w| w.len()>0)
-									.map(|w| w.parse::<u64>().unwrap())
-									.collect()
+						HashSet::from_iter(parse_numbers(winning_numbers_string)?),
+					lottery_numbers: parse_numbers(lottery_numbers_string)?
 				 })
 		} else {
-			Err("Not a card".to_string())
+			Err(error(card, "Not a card"))
 		}
 	}	
 	
@@ -83,7 +81,7 @@ impl ScratchCard {
 	fn point(&self) -> usize {
 		let exponent = self.number_winning_numbers();
 		if exponent > 0 {
-			(1 as usize) << (exponent-1)
+			1 << (exponent-1)
 		} else {
 			0
 		}
@@ -93,7 +91,7 @@ impl ScratchCard {
 		let number_of_coppies = 
 			old_record.current_number_of_copies
 			.get(&self.card_number)
-			.map(|v| *v)
+			.copied()
 			.unwrap_or(1);
 		old_record.update(self.card_number, 
 						  self.number_winning_numbers(), 
@@ -101,35 +99,26 @@ impl ScratchCard {
 	}
 }
 
-pub fn run_day4() {
-	println!("Day 4:");
-	part1(EXAMPLE_DATA_PART1);	
-	part1(INPUT);	
-	part2(EXAMPLE_DATA_PART1);
-	part2(INPUT);
-}
+pub struct Day4;
 
-fn part1(filename: &str) {
-	println!("Part 1: {}", filename);
-	let total_number_of_points = read_cards(filename)
-		.iter().map(|card| card.point()).sum::<usize>();
-	println!("Total number of points: {}", total_number_of_points);
-}
+impl Solution for Day4 {
+	const DAY: usize = 4;
+	type Input = Vec<ScratchCard>;
+	type Answer1 = usize;
+	type Answer2 = usize;
 
-fn part2(filename: &str) {
-	println!("Part 2: {}", filename);
-	let total_number_of_scratch_cards = read_cards(filename)
-		.iter()
-		.fold(CopiesRecord::new(), |record, card| card.process(record))
-		.total_number_of_cards;
-	println!("Total number of scratch cards: {}",
-			 total_number_of_scratch_cards);
-}
+	fn parse(source: &Source) -> Result<Self::Input> {
+		source.parse_lines(ScratchCard::parse)
+	}
 
-fn read_cards(filename: &str) -> Vec<ScratchCard> {
-	let input_file = File::open(filename).unwrap();	
-	let input_buffer = BufReader::new(input_file);
-	input_buffer.lines()
-		.map(|line| ScratchCard::parse(line.unwrap().as_str()).unwrap())
-		.collect()
+	fn part1(cards: &Self::Input) -> usize {
+		cards.iter().map(|card| card.point()).sum()
+	}
+
+	fn part2(cards: &Self::Input) -> usize {
+		cards
+			.iter()
+			.fold(CopiesRecord::new(), |record, card| card.process(record))
+			.total_number_of_cards
+	}
 }
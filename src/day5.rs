@@ -1,51 +1,10 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-const EXAMPLE: &str = "problem_data/day5/example.txt";
-const INPUT: &str = "problem_data/day5/input.txt";
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::interval::{Interval, IntervalSet};
+use crate::solution::Solution;
+use crate::trace::{self, trace, Level};
 
 #[derive(PartialEq, Clone, Copy, Debug)]
-struct Range {
-	start: usize,
-	length: usize
-}
-
-impl Range {
-	fn new(current_start: usize, current_length: usize) -> Self {
-		Range {
-		   start: current_start,
-		   length: current_length
-		}
-	}
-
-	fn end(&self) -> usize {
-		self.start + self.length
-	}
-
-	fn overlap(&self, other: Range) -> (Option<Range>, Vec<Range>) {
-		if self.start <= other.start && self.end() >= other.end() {
-			(Some(other), Vec::new())
-		} else if self.start <= other.start && 
-				  other.start < self.end() && 
-				  self.end() < other.end() {
-			(Some(Range::new(other.start, self.end() - other.start)),
-			 vec![Range::new(self.end(), other.end() - self.end())])
-		} else if self.start > other.start && 
-				  self.start < other.end() && 
-				  self.end() >= other.end(){
-			(Some(Range::new(self.start, other.end() - self.start)),
-			 vec![Range::new(other.start, self.start - other.start)])
-		} else if self.start > other.start && self.end() < other.end(){
-			(Some(Range::new(self.start, self.length)),
-			 vec![Range::new(other.start, self.start - other.start),
-			 	  Range::new(self.end(), other.end() - self.end())])
-		} else {
-			(None, vec![other])
-		}
-	}
-}
-
-#[derive(PartialEq)]
 struct RangeMap {
 	source_start: usize,
 	target_start: usize,	
@@ -53,191 +12,379 @@ struct RangeMap {
 }
 
 impl RangeMap {
-	fn parse(input_string: &str) -> Option<Self> {
-		let (target_start_str, tail) = input_string.split_once(' ')?;
-		let (source_start_str, range_length_str) = tail.split_once(' ')?;
-		Some(RangeMap {
-				source_start: source_start_str.parse::<usize>().unwrap(),
-				target_start: target_start_str.parse::<usize>().unwrap(),
-				range_length: range_length_str.parse::<usize>().unwrap()
+	fn parse(input_string: &str) -> Result<Self> {
+		let words: Vec<&str> = input_string.split(' ').filter(|word| !word.is_empty()).collect();
+		let numbers = words
+			.iter()
+			.map(|word| word.parse::<usize>()
+				 .map_err(|_| Error::parse(input_string, word, "Not a number")))
+			.collect::<Result<Vec<usize>>>()?;
+		if numbers.len() != 3 {
+			return Err(Error::parse(input_string, input_string, 
+									"Expected <target start> <source start> <length>"));
+		}
+		if numbers[0].max(numbers[1]).checked_add(numbers[2]).is_none() {
+			return Err(Error::parse(input_string, words[2], "Range end overflows"));
+		}
+		Ok(RangeMap {
+				source_start: numbers[1],
+				target_start: numbers[0],
+				range_length: numbers[2]
 			 })
 	}
 
 	fn transform(&self, value: usize) -> Option<usize> {
-		if self.source_start <= value && 
-			value <= self.source_start + self.range_length {
+		if self.source().contains(value) {
 			Some(self.target_start + (value - self.source_start))
 		} else {
 			None
-		}		
+		}
+	}
+
+	fn source(&self) -> Interval {
+		Interval::with_length(self.source_start, self.range_length)
+	}
+
+	fn target(&self) -> Interval {
+		Interval::with_length(self.target_start, self.range_length)
 	}
 
-	fn transform_range(&self, range: Range) -> (Option<Range>, Vec<Range>) { 
-		let source_range = Range {
-			start: self.source_start, 
-			length: self.range_length
-		};	
-		let (overlap, remainder) = source_range.overlap(range);
-		(overlap.map(|o| Range::new(self.target_start + (o.start - self.source_start), 
-			o.length)), remainder)
+	fn restrict(&self, source: Interval) -> RangeMap {
+		RangeMap {
+			source_start: source.start,
+			target_start: self.target_start + (source.start - self.source_start),
+			range_length: source.len()
+		}
+	}
+
+	fn transform_range(&self, ranges: &IntervalSet) -> (IntervalSet, IntervalSet) {
+		let source = IntervalSet::from(self.source());
+		let transformed = ranges
+			.intersection(&source)
+			.iter()
+			.map(|overlap| Interval::with_length(self.target_start + (overlap.start - self.source_start),
+												 overlap.len()))
+			.collect();
+		(transformed, ranges.difference(&source))
 	}
 }
 
+#[derive(Debug)]
 struct IndexMap {
+	source: String,
+	target: String,
 	ranges: Vec<RangeMap>
 }
 
 impl IndexMap {
-	fn new() -> Self {
+	fn new(source: &str, target: &str) -> Self {
 		IndexMap {
+			source: source.to_string(),
+			target: target.to_string(),
 			ranges: Vec::new()
 		}
 	}
 
+	fn parse_header(header: &str) -> Result<Self> {
+		let categories = header.strip_suffix(" map:").unwrap_or(header);
+		match categories.split_once("-to-") {
+			Some((source, target)) if !source.is_empty() && !target.is_empty() =>
+				Ok(IndexMap::new(source, target)),
+			_ => Err(Error::parse(header, header, "Expected a <source>-to-<target> map header"))
+		}
+	}
+
 	fn add_range(&mut self, range: RangeMap) {
 		self.ranges.push(range);
 	}
 
 	fn transform(&self, value: usize) -> usize {
-		match self.ranges.iter().map(|range| range.transform(value)).find(|x| x.is_some()) {
-			Some(v) => v.unwrap(),
-			None => value
+		self.ranges.iter().find_map(|range| range.transform(value)).unwrap_or(value)
+	}
+
+	fn transform_range(&self, ranges: &IntervalSet) -> IntervalSet {
+		let mut transformed = IntervalSet::new();
+		let mut remainder = ranges.clone();
+		for range_map in &self.ranges {
+			let (mapped, unmapped) = range_map.transform_range(&remainder);
+			transformed = transformed.union(&mapped);
+			remainder = unmapped;
 		}
+		let result = transformed.union(&remainder);
+		trace!(Level::Details, "{}-to-{}: {} -> {}", self.source, self.target, ranges, result);
+		result
 	}
 
-	fn transform_range(&self, range: Range) -> Vec<Range> {
-		let mut result_vec: Vec<Range> = Vec::new();
-		let mut reminder: Vec<Range> = self.ranges
-			.iter()
-			.fold(vec![range], 
-				  |ranges, range_map| {
-					let mut next_ranges: Vec<Range> = Vec::new();
-					ranges.iter()
-						  .for_each(|range_to_transform| {
-										let (transformed, mut reminder) = 
-											range_map
-											.transform_range(*range_to_transform);
-										if let Some(t) = transformed {
-											result_vec.push(t);
-										}
-										next_ranges.append(&mut reminder);
-									});
-					next_ranges
-				  });
-		result_vec.append(&mut reminder);
-		println!("Transformed {:?} to {:?}", range, result_vec);
-		result_vec
+	fn minimum(&self, ranges: &IntervalSet) -> Option<usize> {
+		self.transform_range(ranges).min()
 	}
+
+	fn pieces(&self) -> Vec<RangeMap> {
+		let mut covered = IntervalSet::new();
+		let mut pieces: Vec<RangeMap> = Vec::new();
+		for range_map in &self.ranges {
+			let uncovered = IntervalSet::from(range_map.source()).difference(&covered);
+			pieces.extend(uncovered.iter().map(|source| range_map.restrict(*source)));
+			covered.insert(range_map.source());
+		}
+		let everything = IntervalSet::from(Interval::new(0, usize::MAX));
+		pieces.extend(everything.difference(&covered).iter().map(|gap| RangeMap {
+			source_start: gap.start,
+			target_start: gap.start,
+			range_length: gap.len()
+		}));
+		pieces.sort_by_key(|piece| piece.source_start);
+		pieces
 	}
 
-struct DataFile {
+	fn compose(&self, next: &IndexMap) -> IndexMap {
+		let next_pieces = next.pieces();
+		let mut ranges: Vec<RangeMap> = Vec::new();
+		for piece in self.pieces() {
+			for next_piece in &next_pieces {
+				if let Some(overlap) = piece.target().intersection(&next_piece.source()) {
+					ranges.push(RangeMap {
+						source_start: piece.source_start + (overlap.start - piece.target_start),
+						target_start: next_piece.target_start + (overlap.start - next_piece.source_start),
+						range_length: overlap.len()
+					});
+				}
+			}
+		}
+		IndexMap {
+			source: self.source.clone(),
+			target: next.target.clone(),
+			ranges
+		}
+	}
+
+	fn invert(&self) -> Option<IndexMap> {
+		let pieces = self.pieces();
+		let mut images: Vec<Interval> = pieces.iter().map(|piece| piece.target()).collect();
+		images.sort();
+		let tiles = images.first().is_some_and(|first| first.start == 0) &&
+			images.windows(2).all(|pair| pair[0].end == pair[1].start) &&
+			images.last().is_some_and(|last| last.end == usize::MAX);
+		if !tiles {
+			return None;
+		}
+		Some(IndexMap {
+			source: self.target.clone(),
+			target: self.source.clone(),
+			ranges: pieces.iter().map(|piece| RangeMap {
+				source_start: piece.target_start,
+				target_start: piece.source_start,
+				range_length: piece.range_length
+			}).collect()
+		})
+	}
+}
+
+pub struct DataFile {
+	name: String,
+	seeds_line: String,
 	seeds: Vec<usize>,
 	index_maps: Vec<IndexMap>
 }
 
 impl DataFile {
-	fn read(filename: &str) -> Option<Self> {
-		let input_file = File::open(filename).ok()?;	
-		let input_buffer = BufReader::new(input_file);
+	fn read(source: &Source) -> Result<Self> {
 		let mut current_seeds: Vec<usize> = Vec::new();
 		let mut current_index_map_list: Vec<IndexMap> = Vec::new();
-		for (i, line) in input_buffer.lines().enumerate() {
-			let row = line.ok()?;
+		for (i, row) in source.lines().iter().enumerate() {
 			if i == 0 {
-				let (_, tail) = row.split_once(':')?;
-				current_seeds = tail.trim().split(' ')
-					.map(|word| word.parse::<usize>().unwrap()).collect();
-			} else {
-				if let Some(range) = RangeMap::parse(row.as_str()) {
-					let len = current_index_map_list.len();
-					current_index_map_list[len - 1].add_range(range);
-				} else if row != "".to_string() {
-					current_index_map_list.push(IndexMap::new());
-				}
+				let tail = row.strip_prefix("seeds:")
+					.ok_or_else(|| source.missing_section("seeds"))?;
+				current_seeds = source.parse_line(i, |row| tail.trim().split(' ')
+					.map(|word| word.parse::<usize>()
+						 .map_err(|_| Error::parse(row, word, "Not a seed")))
+					.collect::<Result<Vec<usize>>>())?;
+			} else if row.ends_with("map:") {
+				let previous_target = current_index_map_list.last()
+					.map_or("seed", |index_map: &IndexMap| index_map.target.as_str());
+				let index_map = source.parse_line(i, |row| {
+					let index_map = IndexMap::parse_header(row)?;
+					if index_map.source != previous_target {
+						return Err(Error::parse(row, &row[..index_map.source.len()],
+												&format!("Expected a map from {:?}", previous_target)));
+					}
+					Ok(index_map)
+				})?;
+				current_index_map_list.push(index_map);
+			} else if !row.is_empty() {
+				let range = source.parse_line(i, RangeMap::parse)?;
+				current_index_map_list
+					.last_mut()
+					.ok_or_else(|| source.missing_section("map"))?
+					.add_range(range);
 			}
 		}
-		Some(DataFile {
+		match current_index_map_list.last() {
+			None => return Err(source.missing_section("map")),
+			Some(index_map) if index_map.target != "location" =>
+				return Err(source.missing_section(&format!("{}-to-location map", index_map.target))),
+			Some(_) => {}
+		}
+		Ok(DataFile {
+			name: source.name().to_string(),
+			seeds_line: source.lines()[0].clone(),
 			seeds: current_seeds,
 			index_maps: current_index_map_list
 		})
 	}
 
-	fn expand_seeds(&self) -> Vec<Range> {
-		let mut expanded_seeds: Vec<Range> = Vec::new();	
-		self.seeds
-			.iter()
-			.enumerate()
-			.fold(0,|previous, (index, value)| {
-					if index % 2 == 1 {
-						expanded_seeds.push(Range::new(previous, *value));
-						0
-					} else {
-						*value
+	/// Reads the seeds as pairs of start and length, which only part 2 does.
+	fn expand_seeds(&self) -> Result<IntervalSet> {
+		let tail = self.seeds_line.strip_prefix("seeds:").unwrap_or("").trim();
+		let words: Vec<&str> = tail.split(' ').collect();
+		let error = |word: &str, message: &str| Error::parse(&self.seeds_line, word, message)
+			.at(&self.name, 1);
+		let mut expanded_seeds: Vec<Interval> = Vec::new();
+		for (pair, words) in self.seeds.chunks(2).zip(words.chunks(2)) {
+			match (pair, words) {
+				([start, length], [_, length_word]) => {
+					if *length == 0 {
+						return Err(error(length_word, "Empty seed range"));
 					}
-				  });
-		expanded_seeds
+					let end = start.checked_add(*length)
+						.ok_or_else(|| error(length_word, "Range end overflows"))?;
+					expanded_seeds.push(Interval::new(*start, end));
+				},
+				_ => return Err(error(words[0], "Seed start without a length"))
+			}
+		}
+		Ok(expanded_seeds.into_iter().collect())
 	}
-}
 
+	fn compose_chain(&self, from: &str, to: &str) -> Option<IndexMap> {
+		let start = self.index_maps.iter().position(|index_map| index_map.source == from)?;
+		let end = self.index_maps.iter().position(|index_map| index_map.target == to)?;
+		(start <= end).then(|| self.index_maps[start..=end]
+			.iter()
+			.fold(IndexMap::new(from, from), |composed, index_map| {
+				trace!(Level::Details, "Composing {}-to-{} map", index_map.source, index_map.target);
+				composed.compose(index_map)
+			}))
+	}
 
-pub fn run_day5() {
-	println!("Day 5:");
-	part1(EXAMPLE);
-	part1(INPUT);
-	part2(EXAMPLE);
-	part2(INPUT);
-}
+	/// Maps between any two categories, going backwards through the chain when `to` comes
+	/// before `from` and the maps on the way are invertible.
+	fn mapping(&self, from: &str, to: &str) -> Result<IndexMap> {
+		self.compose_chain(from, to)
+			.or_else(|| self.compose_chain(to, from)?.invert())
+			.ok_or_else(|| Error::missing_section(&self.name, &format!("{}-to-{} map", from, to)))
+	}
 
-fn part1(filename: &str) {
-	println!("Part 1: {}", filename);
-	let data_file = DataFile::read(filename).unwrap();
-	let transformed_seeds = data_file
-		.index_maps
-		.iter()
-		.fold(data_file.seeds, |current_seeds, index_map| {
-			  current_seeds.iter().map(|index| {
-									   index_map.transform(*index)
-									   }).collect::<Vec<usize>>()
-			  });
-	println!("Min position: {}", transformed_seeds.iter().min().unwrap());
-}
+	fn stage_path(&self, seeds: &IntervalSet) -> String {
+		let mut current = seeds.clone();
+		let mut path = format!("seed {}", current);
+		for index_map in &self.index_maps {
+			current = index_map.transform_range(&current);
+			path += &format!(" -> {} {}", index_map.target, current);
+		}
+		path
+	}
 
-fn part2(filename: &str) {
-	println!("Part 2: {}", filename);
-	let data_file = DataFile::read(filename).unwrap();
-	let seeds = data_file.expand_seeds();
-	let minimum_postion = 
-		seeds.iter().map(|seed_range| {
-						data_file.index_maps
-						         .iter()
-								 .enumerate()
-								 .fold(vec![*seed_range],
-									   |seeds_to_apply, (map_index, index_map)| {
-									   		println!("Applying {}", map_index);
-											seeds_to_apply.iter().flat_map(|seed| {
-																			index_map.transform_range(*seed)
-																		   }).collect::<Vec<Range>>()
-									   }).iter().map(|pos| pos.start).min().unwrap()
-
-						 }).min().unwrap();
-	println!("Min position: {}", minimum_postion);
+	fn trace_stages(&self, seeds: &[Interval]) {
+		if trace::enabled(Level::Stages) {
+			for focused in seeds.iter().filter_map(|seeds| trace::in_focus(*seeds)) {
+				trace!(Level::Stages, "{}", self.stage_path(&IntervalSet::from(focused)));
+			}
+		}
+	}
 }
 
 
+pub struct Day5;
+
+impl Solution for Day5 {
+	const DAY: usize = 5;
+	type Input = DataFile;
+	type Answer1 = usize;
+	type Answer2 = Result<usize>;
+
+	fn parse(source: &Source) -> Result<Self::Input> {
+		DataFile::read(source)
+	}
+
+	fn part1(data_file: &Self::Input) -> usize {
+		data_file.trace_stages(&data_file.seeds.iter()
+							   .map(|seed| Interval::with_length(*seed, 1))
+							   .collect::<Vec<Interval>>());
+		let transformed_seeds = data_file
+			.index_maps
+			.iter()
+			.fold(data_file.seeds.clone(), |current_seeds, index_map| {
+				  current_seeds.iter().map(|index| {
+										   index_map.transform(*index)
+										   }).collect::<Vec<usize>>()
+				  });
+		*transformed_seeds.iter().min().unwrap()
+	}
+
+	fn part2(data_file: &Self::Input) -> Result<usize> {
+		let seeds = data_file.expand_seeds()?;
+		data_file.trace_stages(&seeds.iter().copied().collect::<Vec<Interval>>());
+		// `expand_seeds` rejects empty ranges, so there always is a minimum.
+		Ok(data_file.mapping("seed", "location")?.minimum(&seeds).unwrap())
+	}
+}
 
 #[cfg(test)]
 mod day5_tests {
 	use super::*;
+	use std::collections::BTreeSet;
+	use proptest::prelude::*;
+	use crate::test_support::values;
+
+	fn arbitrary_index_map() -> impl Strategy<Value = IndexMap> {
+		prop::collection::vec((0..40_usize, 0..40_usize, 0..10_usize), 0..5)
+			.prop_map(|ranges| {
+				let mut index_map = IndexMap::new("a", "b");
+				for (target_start, source_start, range_length) in ranges {
+					index_map.add_range(RangeMap { source_start, target_start, range_length });
+				}
+				index_map
+			})
+	}
+
+	fn arbitrary_chain() -> impl Strategy<Value = Vec<IndexMap>> {
+		prop::collection::vec(arbitrary_index_map(), 1..5)
+			.prop_map(|mut index_maps| {
+				let category = |index: usize| match index {
+					0 => "seed".to_string(),
+					index if index == index_maps.len() => "location".to_string(),
+					index => format!("category{}", index)
+				};
+				let names: Vec<(String, String)> = (0..index_maps.len())
+					.map(|index| (category(index), category(index + 1)))
+					.collect();
+				for (index_map, (source, target)) in index_maps.iter_mut().zip(names) {
+					index_map.source = source;
+					index_map.target = target;
+				}
+				index_maps
+			})
+	}
+
 #[test]
 	fn parse_range() {
 		let input_string = "45 23 5";
-		assert!(RangeMap::parse(input_string) == Some(RangeMap {
+		assert!(RangeMap::parse(input_string).ok() == Some(RangeMap {
 			source_start: 23, 
 			target_start: 45,
 			range_length: 5
 			}));
 	}
 
+#[test]
+	fn parse_range_reports_bad_number() {
+		let error = RangeMap::parse("45 2x 5").err().unwrap().at("almanac.txt", 3);
+		assert_eq!(error.to_string(), "almanac.txt:3:4: Not a number: \"2x\"");
+		let error = RangeMap::parse("0 18446744073709551610 10").err().unwrap().at("almanac.txt", 3);
+		assert_eq!(error.to_string(), "almanac.txt:3:24: Range end overflows: \"10\"");
+	}
+
 #[test]
 	fn range_maps_inside_value_correct() {
 		let range = RangeMap {
@@ -247,4 +394,178 @@ mod day5_tests {
 		};
 		assert!(range.transform(47) == Some(25));
 	}
+
+#[test]
+	fn range_maps_are_half_open() {
+		let range = RangeMap::parse("50 98 2").unwrap();
+		assert_eq!(range.transform(97), None);
+		assert_eq!(range.transform(98), Some(50));
+		assert_eq!(range.transform(99), Some(51));
+		assert_eq!(range.transform(100), None);
+		assert_eq!(RangeMap::parse("7 3 0").unwrap().transform(3), None);
+	}
+
+#[test]
+	fn first_matching_entry_wins() {
+		let mut index_map = IndexMap::new("a", "b");
+		index_map.add_range(RangeMap::parse("100 10 5").unwrap());
+		index_map.add_range(RangeMap::parse("200 12 5").unwrap());
+		index_map.add_range(RangeMap::parse("300 17 1").unwrap());
+		let transformed: Vec<usize> = (9..19).map(|value| index_map.transform(value)).collect();
+		assert_eq!(transformed, vec![9, 100, 101, 102, 103, 104, 203, 204, 300, 18]);
+	}
+
+#[test]
+	fn range_maps_split_ranges() {
+		let range = RangeMap {
+			source_start: 10,
+			target_start: 100,
+			range_length: 5
+		};
+		let (transformed, remainder) = range.transform_range(&IntervalSet::from(Interval::new(8, 20)));
+		assert_eq!(transformed, IntervalSet::from(Interval::new(100, 105)));
+		assert_eq!(remainder, [Interval::new(8, 10), Interval::new(15, 20)].into_iter().collect());
+	}
+
+#[test]
+	fn composed_maps_match_sequential_application() {
+		let data_file = Day5::parse(&Source::from_file("problem_data/day5/example.txt").unwrap())
+			.unwrap();
+		let composed = data_file.mapping("seed", "location").unwrap();
+		let pieces = composed.pieces();
+		assert!(pieces.windows(2).all(|pair| pair[0].source().end == pair[1].source_start));
+		for seed in [0, 13, 14, 55, 79, 97, 98, 99, 100, 1000] {
+			let seeds = IntervalSet::from(Interval::with_length(seed, 1));
+			let sequential = data_file.index_maps.iter()
+				.fold(seeds.clone(), |current, index_map| index_map.transform_range(&current));
+			assert_eq!(composed.transform_range(&seeds), sequential, "seed {}", seed);
+		}
+		assert_eq!(composed.minimum(&IntervalSet::from(Interval::with_length(79, 14))), Some(46));
+	}
+
+#[test]
+	fn stage_path_follows_every_category() {
+		let data_file = Day5::parse(&Source::from_file("problem_data/day5/example.txt").unwrap())
+			.unwrap();
+		assert_eq!(data_file.stage_path(&IntervalSet::from(Interval::with_length(79, 1))),
+				   "seed {79} -> soil {81} -> fertilizer {81} -> water {81} -> light {74} \
+				   -> temperature {78} -> humidity {78} -> location {82}");
+		assert_eq!(data_file.stage_path(&IntervalSet::from(Interval::with_length(82, 2))),
+				   "seed {82..84} -> soil {84..86} -> fertilizer {84..86} -> water {84..86} \
+				   -> light {77..79} -> temperature {45..47} -> humidity {46..48} -> location {46..48}");
+	}
+
+#[test]
+	fn inverse_maps_back() {
+		let seed_to_soil = IndexMap {
+			source: "seed".to_string(),
+			target: "soil".to_string(),
+			ranges: vec![RangeMap::parse("50 98 2").unwrap(), RangeMap::parse("52 50 48").unwrap()]
+		};
+		let soil_to_seed = seed_to_soil.invert().unwrap();
+		for seed in [0, 49, 50, 79, 97, 98, 99, 100] {
+			let soil = seed_to_soil.transform_range(&IntervalSet::from(Interval::with_length(seed, 1)));
+			assert_eq!(soil_to_seed.transform_range(&soil).min(), Some(seed));
+		}
+		let mut collapsing = IndexMap::new("seed", "soil");
+		collapsing.add_range(RangeMap::parse("0 10 5").unwrap());
+		assert!(collapsing.invert().is_none());
+	}
+
+#[test]
+	fn maps_between_named_categories() {
+		let data_file = Day5::parse(&Source::from_file("problem_data/day5/example.txt").unwrap())
+			.unwrap();
+		let soil_to_humidity = data_file.mapping("soil", "humidity").unwrap();
+		assert_eq!((soil_to_humidity.source.as_str(), soil_to_humidity.target.as_str()),
+				   ("soil", "humidity"));
+		let soil = IntervalSet::from(Interval::with_length(81, 1));
+		assert_eq!(soil_to_humidity.minimum(&soil), Some(78));
+		let location_to_seed = data_file.mapping("location", "seed").unwrap();
+		assert_eq!((location_to_seed.source.as_str(), location_to_seed.target.as_str()),
+				   ("location", "seed"));
+		assert_eq!(location_to_seed.transform_range(&IntervalSet::from(Interval::with_length(46, 1))).min(),
+				   Some(82));
+		let error = data_file.mapping("soil", "fuel").err().unwrap();
+		assert_eq!(error.to_string(),
+				   "problem_data/day5/example.txt: missing section \"soil-to-fuel map\"");
+	}
+
+#[test]
+	fn rejects_broken_chains() {
+		let read = |text: &str| DataFile::read(&Source::from_text("almanac", text)).err().unwrap().to_string();
+		assert_eq!(read("seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nwater-to-light map:\n1 2 3"),
+				   "almanac:6:1: Expected a map from \"soil\": \"water\"");
+		assert_eq!(read("seeds: 1 2\n\nsoil-to-water map:\n1 2 3"),
+				   "almanac:3:1: Expected a map from \"seed\": \"soil\"");
+		assert_eq!(read("seeds: 1 2\n\nseed to soil map:\n1 2 3"),
+				   "almanac:3:1: Expected a <source>-to-<target> map header: \"seed to soil map:\"");
+		assert_eq!(read("seeds: 1 2\n\n1 2 3"), "almanac: missing section \"map\"");
+		assert_eq!(read("seeds: 79\n\nseed-to-soil map:\n50 98 2"),
+				   "almanac: missing section \"soil-to-location map\"");
+	}
+
+#[test]
+	fn example_answers() {
+		let data_file = Day5::parse(&Source::from_file("problem_data/day5/example.txt").unwrap())
+			.unwrap();
+		assert_eq!(Day5::part1(&data_file), 35);
+		assert_eq!(Day5::part2(&data_file).unwrap(), 46);
+	}
+
+#[test]
+	fn part2_checks_seed_pairs() {
+		let almanac = |seeds: &str| Day5::parse(&Source::from_text("almanac", &format!(
+			"seeds: {}\n\nseed-to-location map:\n50 98 2", seeds))).unwrap();
+		let part2 = |seeds: &str| Day5::part2(&almanac(seeds)).map_err(|error| error.to_string());
+		assert_eq!(Day5::part1(&almanac("79 14 55")), 14);
+		assert_eq!(part2("79 14 55"), Err("almanac:1:14: Seed start without a length: \"55\"".to_string()));
+		assert_eq!(part2("79 0"), Err("almanac:1:11: Empty seed range: \"0\"".to_string()));
+		assert_eq!(part2("18446744073709551610 10"),
+				   Err("almanac:1:29: Range end overflows: \"10\"".to_string()));
+		assert_eq!(part2("79 14"), Ok(79));
+	}
+
+	proptest! {
+#[test]
+		fn transform_range_matches_point_transform(index_map in arbitrary_index_map(),
+												   start in 0..60_usize, length in 0..20_usize) {
+			let seeds = IntervalSet::from(Interval::with_length(start, length));
+			let expected: BTreeSet<usize> = (start..start + length)
+				.map(|value| index_map.transform(value))
+				.collect();
+			prop_assert_eq!(values(&index_map.transform_range(&seeds)), expected);
+		}
+
+#[test]
+		fn composition_matches_sequential_transform(first in arbitrary_index_map(),
+													second in arbitrary_index_map(),
+													value in 0..60_usize) {
+			let composed = first.compose(&second);
+			prop_assert_eq!(composed.transform(value), second.transform(first.transform(value)));
+			let pieces = composed.pieces();
+			prop_assert!(pieces.windows(2).all(|pair| pair[0].source().end == pair[1].source_start));
+		}
+
+#[test]
+		fn chained_maps_agree(index_maps in arbitrary_chain(),
+							  start in 0..60_usize, length in 0..20_usize) {
+			let data_file = DataFile {
+				name: "almanac".to_string(),
+				seeds_line: String::new(),
+				seeds: Vec::new(),
+				index_maps
+			};
+			let seeds = IntervalSet::from(Interval::with_length(start, length));
+			let pointwise: BTreeSet<usize> = (start..start + length)
+				.map(|seed| data_file.index_maps.iter()
+					 .fold(seed, |value, index_map| index_map.transform(value)))
+				.collect();
+			let sequential = data_file.index_maps.iter()
+				.fold(seeds.clone(), |current, index_map| index_map.transform_range(&current));
+			prop_assert_eq!(values(&sequential), pointwise.clone());
+			let composed = data_file.mapping("seed", "location").unwrap();
+			prop_assert_eq!(values(&composed.transform_range(&seeds)), pointwise);
+		}
+	}
 }
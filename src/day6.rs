@@ -1,101 +1,359 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::solution::Solution;
 
-const EXAMPLE: &str = "problem_data/day6/example.txt";
-const INPUT: &str = "problem_data/day6/input.txt";
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChargePhase {
+	pub duration_ms: u64,
+	pub rate: u64
+}
+
+/// How holding the button translates into distance. The last charge phase lasts until
+/// the button is released, and the boat loses `drag` mm/ms of speed every ms it moves.
+/// The distance must rise strictly up to its maximum and never rise after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaceModel {
+	pub phases: Vec<ChargePhase>,
+	pub max_speed: Option<u64>,
+	pub drag: u64
+}
+
+impl Default for RaceModel {
+	fn default() -> Self {
+		RaceModel {
+			phases: vec![ChargePhase { duration_ms: u64::MAX, rate: 1 }],
+			max_speed: None,
+			drag: 0
+		}
+	}
+}
+
+impl RaceModel {
+	fn speed(&self, press_time: u64) -> u128 {
+		let mut remaining = press_time;
+		let mut speed: u128 = 0;
+		for (index, phase) in self.phases.iter().enumerate() {
+			let duration = if index + 1 == self.phases.len() {
+				remaining
+			} else {
+				remaining.min(phase.duration_ms)
+			};
+			speed += phase.rate as u128 * duration as u128;
+			remaining -= duration;
+		}
+		self.max_speed.map_or(speed, |max_speed| speed.min(max_speed as u128))
+	}
+
+	fn distance(&self, press_time: u64, time_limit: u64) -> u128 {
+		let speed = self.speed(press_time);
+		let travel_time = (time_limit - press_time) as u128;
+		if self.drag == 0 {
+			return speed * travel_time;
+		}
+		let drag = self.drag as u128;
+		let moving = travel_time.min(speed.div_ceil(drag));
+		moving * speed - drag * moving * moving.saturating_sub(1) / 2
+	}
+}
 
 struct BoatRace {
-	time_limit_ms: usize,
-	record_distance: usize
+	time_limit_ms: u64,
+	record_distance: u64
 }
 
 impl BoatRace {
-	fn new(time_limit: usize, record: usize) -> Self {
+	fn new(time_limit: u64, record: u64) -> Self {
 		BoatRace {
 			time_limit_ms: time_limit,
-			record_distance: record	   
+			record_distance: record
 		}
 	}
 
-	fn distance(&self, press_time: usize) -> usize {
-		press_time*(self.time_limit_ms - press_time)	
+	fn beats_record(&self, model: &RaceModel, press_time: u64) -> bool {
+		model.distance(press_time, self.time_limit_ms) > self.record_distance as u128
+	}
+
+	fn margin_of_error(&self) -> u64 {
+		self.margin_of_error_with(&RaceModel::default())
+	}
+
+	fn margin_of_error_with(&self, model: &RaceModel) -> u64 {
+		if *model == RaceModel::default() {
+			return self.closed_form_margin();
+		}
+		let peak = first_true(0, self.time_limit_ms, |press_time|
+			model.distance(press_time, self.time_limit_ms) >= model.distance(press_time + 1, self.time_limit_ms));
+		if !self.beats_record(model, peak) {
+			return 0;
+		}
+		let first = first_true(0, peak, |press_time| self.beats_record(model, press_time));
+		let after_last = first_true(peak, self.time_limit_ms + 1,
+									|press_time| !self.beats_record(model, press_time));
+		after_last - first
 	}
 
-	fn margin_of_error(&self) -> usize {
-		let tm = self.time_limit_ms as f64;
-		let dr = self.record_distance as f64;
-		let variance = (tm*tm*0.25 - dr).sqrt();
-		let lower_limit = (tm*0.5 - variance).ceil() as usize;
-		let upper_limit = (tm*0.5 + variance).floor() as usize;
-		let mut result = 1+upper_limit - lower_limit;
-		if self.distance(lower_limit) == self.record_distance {
-			result = result - 1;
+	fn closed_form_margin(&self) -> u64 {
+		let time = self.time_limit_ms as u128;
+		let discriminant = match (time * time).checked_sub(4 * self.record_distance as u128) {
+			Some(discriminant) => discriminant,
+			None => return 0
+		};
+		let model = RaceModel::default();
+		let mut lower_limit = ((time - discriminant.isqrt()) / 2) as u64;
+		while lower_limit > 0 && self.beats_record(&model, lower_limit - 1) {
+			lower_limit -= 1;
 		}
-		if self.distance(upper_limit) == self.record_distance {
-			result = result - 1;
+		while lower_limit <= self.time_limit_ms / 2 && !self.beats_record(&model, lower_limit) {
+			lower_limit += 1;
+		}
+		if lower_limit > self.time_limit_ms / 2 {
+			0
+		} else {
+			self.time_limit_ms - 2 * lower_limit + 1
 		}
-		println!("BoatRace({}, {}).margin_of_error() = {}",
-				 self.time_limit_ms, self.record_distance,
-				 result);
-		result
 	}
 }
 
-fn extract_list(line: &String) -> Vec<usize> {
-	line		
-		.split_once(':')
-		.unwrap().1.trim().split(' ')
-		.filter(|word| *word != "")
-		.map(|w| w.parse::<usize>().unwrap())
-		.collect()
+/// The first value in `low..high` where the monotone `predicate` holds, or `high`.
+fn first_true<P: Fn(u64) -> bool>(mut low: u64, mut high: u64, predicate: P) -> u64 {
+	while low < high {
+		let middle = low + (high - low) / 2;
+		if predicate(middle) {
+			high = middle;
+		} else {
+			low = middle + 1;
+		}
+	}
+	low
 }
 
-fn read_full_number(number_str: &String) -> usize {
-	let (_, number_part) = number_str.split_once(':').unwrap();
-	number_part.chars().filter(|c| *c != ' ').collect::<String>().parse::<usize>().unwrap()
+struct TableRow {
+	index: usize,
+	label: String,
+	numbers: Vec<u64>,
+	digits: String
 }
 
-fn read_boat_races(filename: &str) -> Vec<BoatRace> {
-	let input_file = File::open(filename).unwrap();		
-	let input_buffer = BufReader::new(input_file);
-	let data_lines: Vec<String> = 
-		input_buffer.lines().map(|line| line.unwrap()).take(2).collect();
-	let time_limits: Vec<usize> = extract_list(&data_lines[0]);
-	let record_distances: Vec<usize> = extract_list(&data_lines[1]);
-	time_limits.iter().zip(record_distances.iter())
-		.map(|(time, record)| BoatRace::new(*time, *record)).collect()
+impl TableRow {
+	fn parse(index: usize, line: &str) -> Result<Self> {
+		let (label, cells) = line.split_once(':')
+			.ok_or_else(|| Error::parse(line, line, "Missing label"))?;
+		let numbers = cells.split_whitespace()
+			.map(|cell| cell.parse::<u64>().map_err(|_| Error::parse(line, cell, "Not a number")))
+			.collect::<Result<Vec<u64>>>()?;
+		Ok(TableRow {
+			index,
+			label: label.trim().to_string(),
+			numbers,
+			digits: cells.split_whitespace().collect()
+		})
+	}
 }
 
-fn read_boat_race(filename: &str) -> BoatRace {
-	let input_file = File::open(filename).unwrap();		
-	let input_buffer = BufReader::new(input_file);
-	let data_lines: Vec<String> =
-		input_buffer.lines().map(|line| line.unwrap()).take(2).collect();
-	let time_limit = read_full_number(&data_lines[0]);
-	println!("time_limit: {}", time_limit);
-	let record_distance = read_full_number(&data_lines[1]);
-	println!("record_distance: {}", record_distance);
-	BoatRace::new(time_limit, record_distance)
+struct Table {
+	name: String,
+	rows: Vec<TableRow>
 }
 
-pub fn run_day6() {
-	println!("Day 6");
-	part1(EXAMPLE);	
-	part1(INPUT);	
-	part2(EXAMPLE);	
-	part2(INPUT);	
+impl Table {
+	fn parse(source: &Source) -> Result<Self> {
+		let mut rows: Vec<TableRow> = Vec::new();
+		for (index, line) in source.lines().iter().enumerate() {
+			if line.trim().is_empty() {
+				continue;
+			}
+			let row = source.parse_line(index, |line| TableRow::parse(index, line))?;
+			if let Some(first) = rows.first() {
+				if row.numbers.len() != first.numbers.len() {
+					let message = format!("Expected {} columns like {:?}, found {}",
+										  first.numbers.len(), first.label, row.numbers.len());
+					return Err(Error::parse(line, line, &message).at(source.name(), index + 1));
+				}
+			}
+			rows.push(row);
+		}
+		Ok(Table { name: source.name().to_string(), rows })
+	}
+
+	fn row(&self, label: &str) -> Result<&TableRow> {
+		self.rows.iter()
+			.find(|row| row.label == label)
+			.ok_or_else(|| Error::missing_section(&self.name, label))
+	}
+
+	fn columns(&self, label: &str) -> Result<&[u64]> {
+		Ok(&self.row(label)?.numbers)
+	}
+
+	fn concatenated(&self, label: &str) -> Result<u64> {
+		let row = self.row(label)?;
+		row.digits.parse::<u64>()
+			.map_err(|_| Error::parse(&row.digits, &row.digits, "Concatenated number too large")
+					 .at(&self.name, row.index + 1))
+	}
 }
 
-fn part1(filename: &str) {
-	println!("Part 1: {}", filename);
-	let races = read_boat_races(filename);
-	let margin_of_error_product: usize = races.iter().map(|race| race.margin_of_error()).product();
-	println!("product: {}", margin_of_error_product);
+pub struct RaceSheet {
+	races: Vec<BoatRace>,
+	table: Table
 }
 
-fn part2(filename: &str) {
-	println!("Part 2: {}", filename);
-	let race = read_boat_race(filename);
-	println!("{}", race.margin_of_error());
+pub struct Day6;
+
+impl Solution for Day6 {
+	const DAY: usize = 6;
+	type Input = RaceSheet;
+	type Answer1 = u64;
+	type Answer2 = Result<u64>;
+
+	fn parse(source: &Source) -> Result<Self::Input> {
+		let table = Table::parse(source)?;
+		let races = table.columns("Time")?.iter()
+			.zip(table.columns("Distance")?.iter())
+			.map(|(time, record)| BoatRace::new(*time, *record))
+			.collect();
+		Ok(RaceSheet { races, table })
+	}
+
+	fn part1(race_sheet: &Self::Input) -> u64 {
+		race_sheet.races.iter().map(|race| race.margin_of_error()).product()
+	}
+
+	/// Reads the sheet as one race, which can overflow where the separate races of part 1 do not.
+	fn part2(race_sheet: &Self::Input) -> Result<u64> {
+		let table = &race_sheet.table;
+		Ok(BoatRace::new(table.concatenated("Time")?, table.concatenated("Distance")?).margin_of_error())
+	}
 }
 
+#[cfg(test)]
+mod day6_tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	fn brute_force(race: &BoatRace, model: &RaceModel) -> u64 {
+		(0..=race.time_limit_ms).filter(|press_time| race.beats_record(model, *press_time)).count() as u64
+	}
+
+	fn arbitrary_model() -> impl Strategy<Value = RaceModel> {
+		(prop::collection::vec((0..20_u64, 1..4_u64), 1..4), prop::option::of(1..60_u64), 0..4_u64)
+			.prop_map(|(phases, max_speed, drag)| RaceModel {
+				phases: phases.into_iter()
+					.map(|(duration_ms, rate)| ChargePhase { duration_ms, rate })
+					.collect(),
+				max_speed,
+				drag
+			})
+	}
+
+#[test]
+	fn example_races() {
+		let margins: Vec<u64> = [(7, 9), (15, 40), (30, 200), (71530, 940200)].iter()
+			.map(|(time, record)| BoatRace::new(*time, *record).margin_of_error())
+			.collect();
+		assert_eq!(margins, vec![4, 8, 9, 71503]);
+	}
+
+#[test]
+	fn example_sheet() {
+		let sheet = Day6::parse(&Source::from_text("sheet", "Time:      7  15   30\nDistance:  9  40  200"))
+			.unwrap();
+		assert_eq!((Day6::part1(&sheet), Day6::part2(&sheet).unwrap()), (288, 71503));
+	}
+
+#[test]
+	fn table_columns_and_concatenation() {
+		let source = Source::from_text("sheet", "Time: 7 15 30\n\nDistance: 9 40 200\nFuel: 1 2 3");
+		let table = Table::parse(&source).unwrap();
+		assert_eq!(table.columns("Distance").unwrap(), vec![9, 40, 200]);
+		assert_eq!(table.concatenated("Time").unwrap(), 71530);
+		assert_eq!(table.columns("Fuel").unwrap(), vec![1, 2, 3]);
+		assert_eq!(table.columns("Wind").err().unwrap().to_string(), "sheet: missing section \"Wind\"");
+	}
+
+#[test]
+	fn table_errors_are_located() {
+		let error = |text: &str| Table::parse(&Source::from_text("sheet", text)).err().unwrap().to_string();
+		assert_eq!(error("Time: 7 15 30\nDistance: 9 40"),
+				   "sheet:2:1: Expected 3 columns like \"Time\", found 2: \"Distance: 9 40\"");
+		assert_eq!(error("Time: 7 1x"), "sheet:1:9: Not a number: \"1x\"");
+		assert_eq!(error("Time 7"), "sheet:1:1: Missing label: \"Time 7\"");
+		let table = Table::parse(&Source::from_text("sheet", "Time: 7\nDistance: 99999999999 99999999999"));
+		assert!(table.is_err());
+		let table = Table::parse(&Source::from_text("sheet", "Distance: 9999999999 9999999999")).unwrap();
+		assert_eq!(table.concatenated("Distance").err().unwrap().to_string(),
+				   "sheet:1:1: Concatenated number too large: \"99999999999999999999\"");
+	}
+
+#[test]
+	fn part1_survives_concatenation_overflow() {
+		let sheet = Day6::parse(&Source::from_text("sheet", "\
+Time:      7  15   30 1000000000 1000000000
+Distance:  9  40  200 249999999999999999 249999999999999999")).unwrap();
+		assert_eq!(Day6::part1(&sheet), 288);
+		assert_eq!(Day6::part2(&sheet).err().unwrap().to_string(),
+				   "sheet:1:1: Concatenated number too large: \"7153010000000001000000000\"");
+	}
+
+#[test]
+	fn unbeatable_and_touching_records() {
+		assert_eq!(BoatRace::new(10, 25).margin_of_error(), 0);
+		assert_eq!(BoatRace::new(10, 24).margin_of_error(), 1);
+		assert_eq!(BoatRace::new(10, 1000).margin_of_error(), 0);
+		assert_eq!(BoatRace::new(0, 0).margin_of_error(), 0);
+		assert_eq!(BoatRace::new(1, 0).margin_of_error(), 0);
+		assert_eq!(BoatRace::new(2, 0).margin_of_error(), 1);
+	}
+
+#[test]
+	fn race_models() {
+		let race = BoatRace::new(30, 200);
+		let default_by_search = RaceModel {
+			phases: vec![ChargePhase { duration_ms: 5, rate: 1 }, ChargePhase { duration_ms: 0, rate: 1 }],
+			..RaceModel::default()
+		};
+		assert_eq!(race.margin_of_error_with(&default_by_search), 9);
+		let capped = RaceModel { max_speed: Some(12), ..RaceModel::default() };
+		assert_eq!(capped.distance(20, 30), 120);
+		assert_eq!(race.margin_of_error_with(&capped), brute_force(&race, &capped));
+		let dragged = RaceModel { drag: 2, ..RaceModel::default() };
+		assert_eq!(dragged.distance(5, 30), 5 + 3 + 1);
+		let boosted = RaceModel {
+			phases: vec![ChargePhase { duration_ms: 3, rate: 1 }, ChargePhase { duration_ms: 0, rate: 4 }],
+			..RaceModel::default()
+		};
+		assert_eq!(boosted.speed(5), 3 + 2 * 4);
+		assert_eq!(race.margin_of_error_with(&boosted), brute_force(&race, &boosted));
+	}
+
+#[test]
+	fn huge_races_are_exact() {
+		let time = 4_000_000_000_u64;
+		for press_time in [1, 999_999_999, 1_000_000_000, 1_999_999_999] {
+			let record = press_time * (time - press_time);
+			assert_eq!(BoatRace::new(time, record).margin_of_error(), time - 2 * press_time - 1);
+			assert_eq!(BoatRace::new(time, record - 1).margin_of_error(), time - 2 * press_time + 1);
+		}
+	}
+
+	proptest! {
+#[test]
+		fn matches_brute_force(time in 0..2000_u64, fraction in 0.0..1.1_f64) {
+			let record = ((time * time / 4) as f64 * fraction) as u64;
+			let race = BoatRace::new(time, record);
+			prop_assert_eq!(race.margin_of_error(), brute_force(&race, &RaceModel::default()));
+		}
+
+#[test]
+		fn matches_brute_force_near_the_peak(time in 0..2000_u64, below in 0..3_u64) {
+			let race = BoatRace::new(time, (time * time / 4).saturating_sub(below));
+			prop_assert_eq!(race.margin_of_error(), brute_force(&race, &RaceModel::default()));
+		}
+
+#[test]
+		fn models_match_brute_force(model in arbitrary_model(), time in 0..300_u64, record in 0..3000_u64) {
+			let race = BoatRace::new(time, record);
+			prop_assert_eq!(race.margin_of_error_with(&model), brute_force(&race, &model));
+		}
+	}
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+	Io {
+		filename: String,
+		source: io::Error
+	},
+	Parse {
+		filename: String,
+		line: usize,
+		column: usize,
+		text: String,
+		message: String
+	},
+	MissingSection {
+		filename: String,
+		section: String
+	},
+	NoSuchPart {
+		day: usize,
+		part: usize
+	},
+	Config(String),
+	Http {
+		url: String,
+		message: String
+	},
+	Refused(String)
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+	pub fn io(filename: &str, source: io::Error) -> Self {
+		Error::Io {
+			filename: filename.to_string(),
+			source
+		}
+	}
+
+	/// The column is where `text` starts within `line`. Text that is not a slice of `line` is
+	/// reported at column 1, use `parse_at_column` when the line itself is no longer at hand.
+	pub fn parse(line: &str, text: &str, message: &str) -> Self {
+		let bounds = line.as_bytes().as_ptr_range();
+		let inside = bounds.start <= text.as_ptr() && text.as_bytes().as_ptr_range().end <= bounds.end;
+		let column = if inside { text.as_ptr() as usize - line.as_ptr() as usize + 1 } else { 1 };
+		Error::parse_at_column(column, text, message)
+	}
+
+	pub fn parse_at_column(column: usize, text: &str, message: &str) -> Self {
+		Error::Parse {
+			filename: String::new(),
+			line: 0,
+			column,
+			text: text.to_string(),
+			message: message.to_string()
+		}
+	}
+
+	pub fn missing_section(filename: &str, section: &str) -> Self {
+		Error::MissingSection {
+			filename: filename.to_string(),
+			section: section.to_string()
+		}
+	}
+
+	/// Attaches a file name and one based line number to a parse error.
+	pub fn at(self, filename: &str, line_number: usize) -> Self {
+		match self {
+			Error::Parse { column, text, message, .. } => Error::Parse {
+				filename: filename.to_string(),
+				line: line_number,
+				column,
+				text,
+				message
+			},
+			otherwise => otherwise
+		}
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Io { filename, source } => write!(f, "{}: {}", filename, source),
+			Error::Parse { filename, line, column, text, message } =>
+				write!(f, "{}:{}:{}: {}: {:?}", filename, line, column, message, text),
+			Error::MissingSection { filename, section } =>
+				write!(f, "{}: missing section {:?}", filename, section),
+			Error::NoSuchPart { day, part } => write!(f, "Day {} has no part {}", day, part),
+			Error::Config(message) => write!(f, "Configuration: {}", message),
+			Error::Http { url, message } => write!(f, "{}: {}", url, message),
+			Error::Refused(message) => write!(f, "Refused: {}", message)
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Error::Io { source, .. } => Some(source),
+			_ => None
+		}
+	}
+}
+
+#[cfg(test)]
+mod error_tests {
+	use super::*;
+
+#[test]
+	fn parse_error_column_from_slice() {
+		let line = "Game 12: 3 blue, x red";
+		let token = &line[17..18];
+		let error = Error::parse(line, token, "Not a count").at("day2.txt", 4);
+		assert_eq!(error.to_string(), "day2.txt:4:18: Not a count: \"x\"");
+	}
+
+#[test]
+	fn parse_error_unrelated_text() {
+		let error = Error::parse("abc", "elsewhere", "oops").at("f", 1);
+		assert_eq!(error.to_string(), "f:1:1: oops: \"elsewhere\"");
+		let line = "abc";
+		let error = Error::parse(&line[1..], &line[..2], "oops").at("f", 1);
+		assert_eq!(error.to_string(), "f:1:1: oops: \"ab\"");
+	}
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+
+use crate::client::Client;
+use crate::config::YEAR;
+use crate::error::{Error, Result};
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+	Cached,
+	Downloaded
+}
+
+pub fn fetch_input(client: &Client, day: usize, destination: &str) -> Result<Fetched> {
+	let destination_path = Path::new(destination);
+	if destination_path.is_file() {
+		return Ok(Fetched::Cached);
+	}
+	let body = client.get(&format!("/{}/day/{}/input", YEAR, day))?;
+	if let Some(directory) = destination_path.parent() {
+		fs::create_dir_all(directory).map_err(|error| Error::io(destination, error))?;
+	}
+	let partial = format!("{}.part", destination);
+	fs::write(&partial, body).map_err(|error| Error::io(&partial, error))?;
+	fs::rename(&partial, destination).map_err(|error| Error::io(destination, error))?;
+	Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod fetch_tests {
+	use super::*;
+	use crate::client::test_server;
+	use crate::config::Config;
+	use crate::test_support::TempDir;
+
+	fn client(base_url: String) -> Client {
+		Client::new(&Config { session: Some("s3cr3t".to_string()), base_url, ..Config::default() })
+	}
+
+#[test]
+	fn downloads_once_then_uses_cache() {
+		let (base_url, requests) = test_server::serve(vec![(200, "1 2 3\n".to_string())]);
+		let directory = TempDir::new("fetch");
+		let destination = directory.path().join("day7/input.txt");
+		let destination = destination.to_str().unwrap();
+		let client = client(base_url);
+		assert_eq!(fetch_input(&client, 7, destination).unwrap(), Fetched::Downloaded);
+		let request = requests.recv().unwrap();
+		assert!(request.starts_with("GET /2023/day/7/input HTTP/1.1"), "{}", request);
+		assert!(request.contains("session=s3cr3t"), "{}", request);
+		assert_eq!(fs::read_to_string(destination).unwrap(), "1 2 3\n");
+		assert_eq!(fetch_input(&client, 7, destination).unwrap(), Fetched::Cached);
+	}
+
+#[test]
+	fn http_errors_leave_no_file() {
+		let (base_url, _requests) = test_server::serve(vec![(404, "Not found".to_string())]);
+		let directory = TempDir::new("fetch-404");
+		let destination = directory.path().join("input.txt");
+		let result = fetch_input(&client(base_url), 30, destination.to_str().unwrap());
+		assert!(matches!(result, Err(Error::Http { .. })));
+		assert!(!destination.exists());
+	}
+}
@@ -0,0 +1,174 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+use crate::input::Source;
+
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBOURS_8: [(isize, isize); 8] =
+	[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+	width: usize,
+	height: usize,
+	cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+	pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+		if cells.len() == width * height {
+			Some(Grid { width, height, cells })
+		} else {
+			None
+		}
+	}
+
+	pub fn parse<F>(source: &Source, cell: F) -> Result<Self>
+		where F: Fn(char) -> T {
+		let width = source.lines().first().map(|row| row.chars().count()).unwrap_or(0);
+		let rows = source.parse_lines(|row| {
+			let cells: Vec<T> = row.chars().map(&cell).collect();
+			if cells.len() == width {
+				Ok(cells)
+			} else {
+				Err(Error::parse(row, row, "Row length differs from the first row"))
+			}
+		})?;
+		Ok(Grid {
+			width,
+			height: rows.len(),
+			cells: rows.into_iter().flatten().collect()
+		})
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	pub fn contains(&self, row: usize, column: usize) -> bool {
+		row < self.height && column < self.width
+	}
+
+	pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+		if self.contains(row, column) {
+			Some(&self.cells[row * self.width + column])
+		} else {
+			None
+		}
+	}
+
+	pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+		(0..self.height).flat_map(move |row| (0..self.width).map(move |column| (row, column)))
+	}
+
+	pub fn offset(&self, row: usize, column: usize, (d_row, d_column): (isize, isize))
+		-> Option<(usize, usize)> {
+		let row = row.checked_add_signed(d_row)?;
+		let column = column.checked_add_signed(d_column)?;
+		if self.contains(row, column) { Some((row, column)) } else { None }
+	}
+
+	#[allow(dead_code)]
+	pub fn neighbours4(&self, row: usize, column: usize)
+		-> impl Iterator<Item = (usize, usize)> + '_ {
+		NEIGHBOURS_4.iter().filter_map(move |delta| self.offset(row, column, *delta))
+	}
+
+	pub fn neighbours8(&self, row: usize, column: usize)
+		-> impl Iterator<Item = (usize, usize)> + '_ {
+		NEIGHBOURS_8.iter().filter_map(move |delta| self.offset(row, column, *delta))
+	}
+
+	#[allow(dead_code)]
+	pub fn row(&self, row: usize) -> &[T] {
+		&self.cells[row * self.width..(row + 1) * self.width]
+	}
+
+	#[allow(dead_code)]
+	pub fn column(&self, column: usize) -> impl Iterator<Item = &T> + '_ {
+		(0..self.height).map(move |row| &self[(row, column)])
+	}
+
+	pub fn ray(&self, row: usize, column: usize, delta: (isize, isize))
+		-> impl Iterator<Item = &T> + '_ {
+		std::iter::successors(Some((row, column)).filter(|(r, c)| self.contains(*r, *c)),
+							  move |(r, c)| self.offset(*r, *c, delta))
+			.map(move |position| &self[position])
+	}
+
+	#[allow(dead_code)]
+	pub fn diagonal(&self, row: usize, column: usize) -> impl Iterator<Item = &T> + '_ {
+		self.ray(row, column, (1, 1))
+	}
+
+	#[allow(dead_code)]
+	pub fn anti_diagonal(&self, row: usize, column: usize) -> impl Iterator<Item = &T> + '_ {
+		self.ray(row, column, (1, -1))
+	}
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+	type Output = T;
+
+	fn index(&self, (row, column): (usize, usize)) -> &T {
+		assert!(self.contains(row, column), "({}, {}) is outside the grid", row, column);
+		&self.cells[row * self.width + column]
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+	fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+		assert!(self.contains(row, column), "({}, {}) is outside the grid", row, column);
+		&mut self.cells[row * self.width + column]
+	}
+}
+
+#[cfg(test)]
+mod grid_tests {
+	use super::*;
+
+	fn grid() -> Grid<char> {
+		Grid::parse(&Source::from_text("grid", "abc\ndef\nghi"), |c| c).unwrap()
+	}
+
+#[test]
+	fn parse_and_index() {
+		let grid = grid();
+		assert_eq!((grid.width(), grid.height()), (3, 3));
+		assert_eq!(grid[(1, 2)], 'f');
+		assert_eq!(grid.get(2, 0), Some(&'g'));
+		assert_eq!(grid.get(3, 0), None);
+		assert_eq!(grid.get(0, 3), None);
+		assert!(Grid::parse(&Source::from_text("grid", "abc\nde"), |c| c).is_err());
+	}
+
+#[test]
+	fn neighbours_stay_in_bounds() {
+		let grid = grid();
+		assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+		assert_eq!(grid.neighbours8(0, 2).collect::<Vec<_>>(), vec![(0, 1), (1, 1), (1, 2)]);
+		assert_eq!(grid.neighbours8(1, 1).count(), 8);
+	}
+
+#[test]
+	fn rows_columns_and_diagonals() {
+		let grid = grid();
+		assert_eq!(grid.row(1), &['d', 'e', 'f']);
+		assert_eq!(grid.column(2).collect::<String>(), "cfi");
+		assert_eq!(grid.diagonal(0, 0).collect::<String>(), "aei");
+		assert_eq!(grid.diagonal(0, 1).collect::<String>(), "bf");
+		assert_eq!(grid.anti_diagonal(0, 2).collect::<String>(), "ceg");
+		assert_eq!(grid.diagonal(5, 5).count(), 0);
+		assert_eq!(grid.positions().count(), 9);
+	}
+
+#[test]
+	fn new_checks_dimensions() {
+		assert!(Grid::new(2, 2, vec![0; 4]).is_some());
+		assert!(Grid::new(2, 2, vec![0; 3]).is_none());
+	}
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::io::{self, Read};
+
+use crate::error::{Error, Result};
+
+pub const STDIN: &str = "-";
+
+#[derive(Clone)]
+pub struct Source {
+	name: String,
+	lines: Vec<String>
+}
+
+impl Source {
+	pub fn from_file(filename: &str) -> Result<Self> {
+		let text = fs::read_to_string(filename).map_err(|error| Error::io(filename, error))?;
+		Ok(Self::from_text(filename, &text))
+	}
+
+	pub fn from_stdin() -> Result<Self> {
+		let mut text = String::new();
+		io::stdin().read_to_string(&mut text).map_err(|error| Error::io("<stdin>", error))?;
+		Ok(Self::from_text("<stdin>", &text))
+	}
+
+	pub fn from_text(name: &str, text: &str) -> Self {
+		Source {
+			name: name.to_string(),
+			lines: text.lines().map(|line| line.to_string()).collect()
+		}
+	}
+
+	pub fn load(filename: &str) -> Result<Self> {
+		if filename == STDIN {
+			Self::from_stdin()
+		} else {
+			Self::from_file(filename)
+		}
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn lines(&self) -> &[String] {
+		&self.lines
+	}
+
+	pub fn missing_section(&self, section: &str) -> Error {
+		Error::missing_section(&self.name, section)
+	}
+
+	pub fn parse_line<T, F>(&self, index: usize, parse: F) -> Result<T>
+		where F: FnOnce(&str) -> Result<T> {
+		let line = self.lines.get(index).map(|line| line.as_str()).unwrap_or("");
+		parse(line).map_err(|error| error.at(&self.name, index + 1))
+	}
+
+	pub fn parse_lines<T, F>(&self, parse: F) -> Result<Vec<T>>
+		where F: Fn(&str) -> Result<T> {
+		(0..self.lines.len())
+			.map(|index| self.parse_line(index, &parse))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod input_tests {
+	use super::*;
+
+#[test]
+	fn text_source_lines() {
+		let source = Source::from_text("inline", "a\nb\r\n\nc");
+		assert_eq!(source.lines(), ["a", "b", "", "c"]);
+		assert_eq!(source.name(), "inline");
+	}
+
+#[test]
+	fn parse_lines_locates_errors() {
+		let source = Source::from_text("inline", "1\n2\nx");
+		let parse = |line: &str| line.parse::<u32>()
+			.map_err(|_| Error::parse(line, line, "Not a number"));
+		assert_eq!(source.parse_lines(parse).err().unwrap().to_string(),
+				   "inline:3:1: Not a number: \"x\"");
+		let source = Source::from_text("inline", "1\n2");
+		assert_eq!(source.parse_lines(parse).unwrap(), vec![1, 2]);
+	}
+
+#[test]
+	fn missing_file_is_io_error() {
+		assert!(matches!(Source::from_file("problem_data/no_such_file.txt"),
+						 Err(Error::Io { .. })));
+	}
+}
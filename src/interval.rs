@@ -0,0 +1,245 @@
+use std::cmp::{max, min};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Interval {
+	pub start: usize,
+	pub end: usize
+}
+
+impl Interval {
+	pub fn new(start: usize, end: usize) -> Self {
+		assert!(start <= end, "Interval start {} is after its end {}", start, end);
+		Interval { start, end }
+	}
+
+	pub fn with_length(start: usize, length: usize) -> Self {
+		Interval::new(start, start + length)
+	}
+
+	pub fn len(&self) -> usize {
+		self.end - self.start
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.start == self.end
+	}
+
+	pub fn contains(&self, value: usize) -> bool {
+		self.start <= value && value < self.end
+	}
+
+	pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+		let start = max(self.start, other.start);
+		let end = min(self.end, other.end);
+		if start < end { Some(Interval::new(start, end)) } else { None }
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+	intervals: Vec<Interval>
+}
+
+impl IntervalSet {
+	pub fn new() -> Self {
+		IntervalSet::default()
+	}
+
+	fn normalise(mut intervals: Vec<Interval>) -> Self {
+		intervals.retain(|interval| !interval.is_empty());
+		intervals.sort();
+		let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+		for interval in intervals {
+			match merged.last_mut() {
+				Some(last) if interval.start <= last.end => last.end = max(last.end, interval.end),
+				_ => merged.push(interval)
+			}
+		}
+		IntervalSet { intervals: merged }
+	}
+
+	pub fn insert(&mut self, interval: Interval) {
+		let mut intervals = std::mem::take(&mut self.intervals);
+		intervals.push(interval);
+		*self = IntervalSet::normalise(intervals);
+	}
+
+	pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+		IntervalSet::normalise(self.intervals.iter().chain(other.intervals.iter()).copied().collect())
+	}
+
+	pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+		let mut result: Vec<Interval> = Vec::new();
+		let (mut i, mut j) = (0, 0);
+		while i < self.intervals.len() && j < other.intervals.len() {
+			let (a, b) = (self.intervals[i], other.intervals[j]);
+			if let Some(overlap) = a.intersection(&b) {
+				result.push(overlap);
+			}
+			if a.end < b.end { i += 1 } else { j += 1 }
+		}
+		IntervalSet { intervals: result }
+	}
+
+	pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+		let mut result: Vec<Interval> = Vec::new();
+		let mut j = 0;
+		for interval in &self.intervals {
+			let mut start = interval.start;
+			while j < other.intervals.len() && other.intervals[j].end <= start {
+				j += 1;
+			}
+			let mut k = j;
+			while k < other.intervals.len() && other.intervals[k].start < interval.end {
+				let removed = other.intervals[k];
+				if start < removed.start {
+					result.push(Interval::new(start, removed.start));
+				}
+				start = max(start, removed.end);
+				k += 1;
+			}
+			if start < interval.end {
+				result.push(Interval::new(start, interval.end));
+			}
+		}
+		IntervalSet { intervals: result }
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &Interval> + '_ {
+		self.intervals.iter()
+	}
+
+	#[allow(dead_code)]
+	pub fn is_empty(&self) -> bool {
+		self.intervals.is_empty()
+	}
+
+	#[allow(dead_code)]
+	pub fn len(&self) -> usize {
+		self.intervals.iter().map(|interval| interval.len()).sum()
+	}
+
+	#[allow(dead_code)]
+	pub fn contains(&self, value: usize) -> bool {
+		self.intervals.iter().any(|interval| interval.contains(value))
+	}
+
+	pub fn min(&self) -> Option<usize> {
+		self.intervals.first().map(|interval| interval.start)
+	}
+}
+
+impl fmt::Display for Interval {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.len() == 1 {
+			write!(f, "{}", self.start)
+		} else {
+			write!(f, "{}..{}", self.start, self.end)
+		}
+	}
+}
+
+impl fmt::Display for IntervalSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let intervals: Vec<String> = self.intervals.iter().map(|interval| interval.to_string()).collect();
+		write!(f, "{{{}}}", intervals.join(", "))
+	}
+}
+
+impl From<Interval> for IntervalSet {
+	fn from(interval: Interval) -> Self {
+		IntervalSet::normalise(vec![interval])
+	}
+}
+
+impl FromIterator<Interval> for IntervalSet {
+	fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+		IntervalSet::normalise(intervals.into_iter().collect())
+	}
+}
+
+#[cfg(test)]
+mod interval_tests {
+	use super::*;
+	use proptest::prelude::*;
+	use crate::test_support::values;
+
+	fn set(intervals: &[(usize, usize)]) -> IntervalSet {
+		intervals.iter().map(|(start, end)| Interval::new(*start, *end)).collect()
+	}
+
+	fn arbitrary_set() -> impl Strategy<Value = IntervalSet> {
+		prop::collection::vec((0..60_usize, 0..15_usize), 0..6)
+			.prop_map(|intervals| intervals.into_iter()
+					  .map(|(start, length)| Interval::with_length(start, length))
+					  .collect())
+	}
+
+	fn is_normalised(set: &IntervalSet) -> bool {
+		set.iter().all(|interval| !interval.is_empty()) &&
+			set.intervals.windows(2).all(|pair| pair[0].end < pair[1].start)
+	}
+
+#[test]
+	fn intervals_are_half_open() {
+		let interval = Interval::with_length(5, 3);
+		assert_eq!(interval, Interval::new(5, 8));
+		assert!(interval.contains(5) && interval.contains(7) && !interval.contains(8));
+		assert_eq!(interval.intersection(&Interval::new(7, 20)), Some(Interval::new(7, 8)));
+		assert_eq!(interval.intersection(&Interval::new(8, 20)), None);
+		assert_eq!(interval.to_string(), "5..8");
+		assert_eq!(Interval::with_length(79, 1).to_string(), "79");
+	}
+
+#[test]
+	fn normalisation_merges_overlapping_and_adjacent() {
+		assert_eq!(set(&[(5, 8), (1, 3), (3, 4), (7, 10), (12, 12)]), set(&[(1, 4), (5, 10)]));
+		assert_eq!(set(&[(1, 4), (5, 10)]).len(), 8);
+		assert_eq!(set(&[(5, 8), (1, 3)]).min(), Some(1));
+		assert_eq!(set(&[(5, 8), (1, 2)]).to_string(), "{1, 5..8}");
+	}
+
+#[test]
+	fn set_operations() {
+		let a = set(&[(0, 10), (20, 30)]);
+		let b = set(&[(5, 25)]);
+		assert_eq!(a.union(&b), set(&[(0, 30)]));
+		assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+		assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+		assert_eq!(b.difference(&a), set(&[(10, 20)]));
+	}
+
+	proptest! {
+#[test]
+		fn union_matches_model(a in arbitrary_set(), b in arbitrary_set()) {
+			let union = a.union(&b);
+			prop_assert!(is_normalised(&union));
+			prop_assert_eq!(values(&union), values(&a).union(&values(&b)).copied().collect());
+		}
+
+#[test]
+		fn intersection_matches_model(a in arbitrary_set(), b in arbitrary_set()) {
+			let intersection = a.intersection(&b);
+			prop_assert!(is_normalised(&intersection));
+			prop_assert_eq!(values(&intersection),
+							values(&a).intersection(&values(&b)).copied().collect());
+		}
+
+#[test]
+		fn difference_matches_model(a in arbitrary_set(), b in arbitrary_set()) {
+			let difference = a.difference(&b);
+			prop_assert!(is_normalised(&difference));
+			prop_assert_eq!(values(&difference),
+							values(&a).difference(&values(&b)).copied().collect());
+		}
+
+#[test]
+		fn insert_matches_union(a in arbitrary_set(), start in 0..60_usize, length in 0..15_usize) {
+			let interval = Interval::with_length(start, length);
+			let mut inserted = a.clone();
+			inserted.insert(interval);
+			prop_assert_eq!(inserted, a.union(&IntervalSet::from(interval)));
+		}
+	}
+}
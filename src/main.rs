@@ -1,29 +1,302 @@
+mod answer;
+mod bench;
+mod cli;
+mod client;
+mod config;
+mod data;
+mod render;
+mod scaffold;
+mod solution;
+mod submit;
+mod toml_lite;
+mod trace;
 mod day1;
 mod day2;
 mod day3;
 mod day4;
 mod day5;
 mod day6;
+mod error;
+mod fetch;
+mod grid;
+mod input;
+mod interval;
+mod verify;
+#[cfg(test)]
+mod test_support;
 use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use day1::run_day1;
-use day2::run_day2;
-use day3::run_day3;
-use day4::run_day4;
-use day5::run_day5;
-use day6::run_day6;
+use cli::{Command, Options, USAGE};
+use client::Client;
+use config::Config;
+use fetch::Fetched;
+use input::Source;
+use render::Format;
+use solution::Runnable;
+use submit::{History, Verdict};
+use verify::Outcome;
 
-fn main() {
-	for command in env::args().skip(1) {
-		match command.as_str() {
-			"day1" | "1" => run_day1(),
-			"day2" | "2" => run_day2(),
-			"day3" | "3" => run_day3(),
-			"day4" | "4" => run_day4(),
-			"day5" | "5" => run_day5(),
-			"day6" | "6" => run_day6(),
-			otherwise => println!("Skipping: {}", otherwise)
+fn registry() -> Vec<Box<dyn Runnable>> {
+	vec![
+		Box::new(day1::Day1),
+		Box::new(day2::Day2),
+		Box::new(day3::Day3),
+		Box::new(day4::Day4),
+		Box::new(day5::Day5),
+		Box::new(day6::Day6),
+	]
+}
+
+fn select<'a>(solutions: &'a [Box<dyn Runnable>], days: &[usize]) 
+	-> Result<Vec<&'a dyn Runnable>, String> {
+	if days.is_empty() {
+		return Ok(solutions.iter().map(|s| s.as_ref()).collect());
+	}
+	days.iter()
+		.map(|day| solutions.iter()
+			 .find(|s| s.day() == *day)
+			 .map(|s| s.as_ref())
+			 .ok_or(format!("Unknown day: {}", day)))
+		.collect()
+}
+
+fn run(solution: &dyn Runnable, options: &Options, config: &Config, input: Option<&Source>) -> bool {
+	let mut success = true;
+	println!("Day {}:", solution.day());
+	let mut named_inputs: Vec<String> = Vec::new();
+	if options.all_inputs && input.is_none() {
+		match data::named_inputs(solution, &config.data_root) {
+			Ok(inputs) => named_inputs.extend(inputs.into_iter().map(|input| input.filename)),
+			Err(error) => {
+				eprintln!("Error: {}", error);
+				success = false;
+			}
+		}
+	}
+	for part in &options.parts {
+		let sources: Vec<error::Result<Source>> = match input {
+			Some(source) => vec![Ok(source.clone())],
+			None => {
+				let mut filenames = vec![solution.example_file(&config.data_root, *part),
+										 solution.input_file(&config.data_root)];
+				filenames.extend(named_inputs.iter().cloned());
+				filenames.iter()
+					.filter(|filename| Path::new(filename).is_file())
+					.map(|filename| Source::load(filename))
+					.collect()
+			}
+		};
+		for source in sources {
+			let result = source.and_then(|source| {
+				println!("Part {}: {}", part, source.name());
+				solution.run_part(*part, &source)
+			});
+			match result {
+				Ok(run) if options.time => println!("{}  (parse {:.3?}, part {} {:.3?})",
+													 run.answer, run.parse_time,
+													 part, run.solve_time),
+				Ok(run) => println!("{}", run.answer),
+				Err(error) => {
+					eprintln!("Error: {}", error);
+					success = false;
+				}
+			}
+		}
+	}
+	success
+}
+
+fn report_missing(solutions: &[&dyn Runnable], config: &Config, parts: &[usize]) {
+	for missing in solutions.iter()
+		.flat_map(|solution| data::missing_files(*solution, &config.data_root, parts)) {
+		eprintln!("Skipping: {}", missing);
+	}
+}
+
+fn run_verify(solutions: &[&dyn Runnable], options: &Options, config: &Config) -> bool {
+	let checks: Vec<_> = solutions.iter()
+		.flat_map(|solution| verify::verify(*solution, &config.data_root, &options.parts))
+		.collect();
+	for check in &checks {
+		println!("Day {} part {} {}: {}", check.day, check.part, check.source, check.outcome);
+	}
+	let count = |matches: fn(&Outcome) -> bool| checks.iter().filter(|c| matches(&c.outcome)).count();
+	let regressions = count(|o| matches!(o, Outcome::Regression { .. }));
+	let errors = count(|o| matches!(o, Outcome::Error(_)));
+	println!("{} passed, {} regressions, {} errors, {} unrecorded, {} missing",
+			 count(|o| matches!(o, Outcome::Pass(_))),
+			 regressions,
+			 errors,
+			 count(|o| matches!(o, Outcome::Unrecorded(_))),
+			 count(|o| matches!(o, Outcome::Missing(_))));
+	regressions == 0 && errors == 0
+}
+
+fn run_bench(solution: &dyn Runnable, options: &Options, config: &Config,
+			 input: Option<&Source>) -> bool {
+	let input_file = solution.input_file(&config.data_root);
+	let source = match input.cloned().map_or_else(|| Source::load(&input_file), Ok) {
+		Ok(source) => source,
+		Err(error) => {
+			eprintln!("Error: {}", error);
+			return false;
+		}
+	};
+	let mut success = true;
+	for part in &options.parts {
+		match bench::bench(solution, *part, &source, options.runs) {
+			Ok(report) => {
+				println!("Day {} part {} ({}), {} runs", 
+						 solution.day(), part, source.name(), options.runs);
+				println!("  parse: {}", report.parse);
+				println!("  solve: {}", report.solve);
+			},
+			Err(error) => {
+				eprintln!("Error: {}", error);
+				success = false;
+			}
 		}
 	}
+	success
 }
 
+fn run_render(solution: &dyn Runnable, options: &Options, config: &Config,
+			  input: Option<&Source>) -> bool {
+	let format = if options.html { Format::Html } else { Format::Ansi };
+	let result = input.cloned()
+		.map_or_else(|| Source::load(&solution.input_file(&config.data_root)), Ok)
+		.and_then(|source| solution.render(&source, format));
+	match result {
+		Ok(Some(text)) => {
+			print!("{}", text);
+			true
+		},
+		Ok(None) => {
+			eprintln!("Day {} has no renderer", solution.day());
+			false
+		},
+		Err(error) => {
+			eprintln!("Error: {}", error);
+			false
+		}
+	}
+}
+
+fn run_fetch(solutions: &[&dyn Runnable], config: &Config) -> bool {
+	let client = Client::new(config);
+	let mut success = true;
+	for solution in solutions {
+		let destination = solution.input_file(&config.data_root);
+		match fetch::fetch_input(&client, solution.day(), &destination) {
+			Ok(Fetched::Cached) => println!("Day {}: {} already cached", solution.day(), destination),
+			Ok(Fetched::Downloaded) => println!("Day {}: downloaded {}", solution.day(), destination),
+			Err(error) => {
+				eprintln!("Error: {}", error);
+				success = false;
+			}
+		}
+	}
+	success
+}
+
+fn run_submit(solution: &dyn Runnable, part: usize, config: &Config) -> bool {
+	let result = Source::load(&solution.input_file(&config.data_root)).and_then(|source| {
+		let answer = solution.run_part(part, &source)?.answer;
+		println!("Day {} part {}: submitting {}", solution.day(), part, answer);
+		let mut history = History::read(&solution.history_file(&config.data_root))?;
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+		submit::submit(&Client::new(config), &mut history, solution.day(), part, &answer, now)
+	});
+	match result {
+		Ok(verdict) => {
+			println!("Verdict: {}", verdict);
+			verdict == Verdict::Correct
+		},
+		Err(error) => {
+			eprintln!("Error: {}", error);
+			false
+		}
+	}
+}
+
+fn main() {
+	let options = match Options::parse(env::args().skip(1)) {
+		Ok(options) => options,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, USAGE);
+			process::exit(2);
+		}
+	};
+	if options.help {
+		println!("{}", USAGE);
+		return;
+	}
+	let mut config = match Config::load() {
+		Ok(config) => config,
+		Err(error) => {
+			eprintln!("Error: {}", error);
+			process::exit(1);
+		}
+	};
+	if let Some(data_root) = &options.data_root {
+		config.data_root = PathBuf::from(data_root);
+	}
+	if options.command == Command::NewDay {
+		let day = options.days[0];
+		if registry().iter().any(|solution| solution.day() == day) {
+			eprintln!("Day {} is already registered", day);
+			process::exit(1);
+		}
+		match scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), &config.data_root, day) {
+			Ok(created) => created.iter().for_each(|path| println!("Wrote {}", path)),
+			Err(error) => {
+				eprintln!("Error: {}", error);
+				process::exit(1);
+			}
+		}
+		return;
+	}
+	trace::set_verbosity(options.verbosity);
+	trace::set_focus(options.trace);
+	let solutions = registry();
+	let selected = match select(&solutions, &options.days) {
+		Ok(selected) => selected,
+		Err(message) => {
+			eprintln!("{}", message);
+			process::exit(1);
+		}
+	};
+	let input = match options.input.as_deref().map(Source::load).transpose() {
+		Ok(input) => input,
+		Err(error) => {
+			eprintln!("Error: {}", error);
+			process::exit(1);
+		}
+	};
+	let success = match options.command {
+		Command::Run => {
+			if input.is_none() {
+				report_missing(&selected, &config, &options.parts);
+			}
+			!selected.iter()
+				.map(|solution| run(*solution, &options, &config, input.as_ref()))
+				.collect::<Vec<bool>>()
+				.contains(&false)
+		},
+		Command::Verify => run_verify(&selected, &options, &config),
+		Command::Bench => !selected.iter()
+			.map(|solution| run_bench(*solution, &options, &config, input.as_ref()))
+			.collect::<Vec<bool>>()
+			.contains(&false),
+		Command::Fetch => run_fetch(&selected, &config),
+		Command::Submit => run_submit(selected[0], options.parts[0], &config),
+		Command::Render => run_render(selected[0], &options, &config, input.as_ref()),
+		Command::NewDay => unreachable!()
+	};
+	if !success {
+		process::exit(1);
+	}
+}
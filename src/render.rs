@@ -0,0 +1,99 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+	Ansi,
+	Html
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Colour {
+	Green,
+	Red,
+	Yellow,
+	Blue,
+	Grey
+}
+
+impl Colour {
+	fn ansi_code(&self) -> u8 {
+		match self {
+			Colour::Green => 32,
+			Colour::Red => 31,
+			Colour::Yellow => 33,
+			Colour::Blue => 34,
+			Colour::Grey => 90
+		}
+	}
+
+	fn html_class(&self) -> &'static str {
+		match self {
+			Colour::Green => "green",
+			Colour::Red => "red",
+			Colour::Yellow => "yellow",
+			Colour::Blue => "blue",
+			Colour::Grey => "grey"
+		}
+	}
+}
+
+const HTML_STYLE: &str = "\
+body { background: #0f0f23; color: #cccccc; }
+.green { color: #00cc00; font-weight: bold; }
+.red { color: #ff3333; }
+.yellow { color: #ffff66; font-weight: bold; }
+.blue { color: #6699ff; }
+.grey { color: #555555; }";
+
+pub fn escape(format: Format, text: &str) -> String {
+	match format {
+		Format::Ansi => text.to_string(),
+		Format::Html => text
+			.replace('&', "&amp;")
+			.replace('<', "&lt;")
+			.replace('>', "&gt;")
+			.replace('"', "&quot;")
+	}
+}
+
+pub fn paint(format: Format, colour: Colour, text: &str, title: Option<&str>) -> String {
+	match format {
+		Format::Ansi => format!("\x1b[{}m{}\x1b[0m", colour.ansi_code(), text),
+		Format::Html => match title {
+			Some(title) => format!("<span class=\"{}\" title=\"{}\">{}</span>",
+								   colour.html_class(), escape(format, title), escape(format, text)),
+			None => format!("<span class=\"{}\">{}</span>", colour.html_class(), escape(format, text))
+		}
+	}
+}
+
+pub fn document(format: Format, title: &str, body: &str) -> String {
+	match format {
+		Format::Ansi => body.to_string(),
+		Format::Html => format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+								 <title>{}</title>\n<style>\n{}\n</style>\n</head>\n\
+								 <body>\n<pre>\n{}</pre>\n</body>\n</html>\n",
+								escape(format, title), HTML_STYLE, body)
+	}
+}
+
+#[cfg(test)]
+mod render_tests {
+	use super::*;
+
+#[test]
+	fn ansi_wraps_text_in_colour_codes() {
+		assert_eq!(paint(Format::Ansi, Colour::Green, "467", None), "\x1b[32m467\x1b[0m");
+		assert_eq!(document(Format::Ansi, "Day 3", "..."), "...");
+	}
+
+#[test]
+	fn html_escapes_text_and_titles() {
+		assert_eq!(paint(Format::Html, Colour::Yellow, "*", Some("467 * 35 = 16345")),
+				   "<span class=\"yellow\" title=\"467 * 35 = 16345\">*</span>");
+		assert_eq!(paint(Format::Html, Colour::Blue, "<&>", None),
+				   "<span class=\"blue\">&lt;&amp;&gt;</span>");
+		let html = document(Format::Html, "Day 3", "body\n");
+		assert!(html.starts_with("<!DOCTYPE html>"));
+		assert!(html.contains("<title>Day 3</title>"));
+		assert!(html.contains("<pre>\nbody\n</pre>"));
+	}
+}
@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+const MODULE_TEMPLATE: &str = "\
+use crate::error::Result;
+use crate::input::Source;
+use crate::solution::Solution;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+	const DAY: usize = {day};
+	type Input = Vec<String>;
+	type Answer1 = usize;
+	type Answer2 = usize;
+
+	fn parse(source: &Source) -> Result<Self::Input> {
+		Ok(source.lines().to_vec())
+	}
+
+	fn part1(_lines: &Self::Input) -> usize {
+		0
+	}
+
+	fn part2(_lines: &Self::Input) -> usize {
+		0
+	}
+}
+
+#[cfg(test)]
+mod day{day}_tests {
+	use super::*;
+
+	const EXAMPLE: &str = \"\";
+
+#[test]
+#[ignore]
+	fn example_part1() {
+		let input = Day{day}::parse(&Source::from_text(\"example\", EXAMPLE)).unwrap();
+		assert_eq!(Day{day}::part1(&input), 0);
+	}
+
+#[test]
+#[ignore]
+	fn example_part2() {
+		let input = Day{day}::parse(&Source::from_text(\"example\", EXAMPLE)).unwrap();
+		assert_eq!(Day{day}::part2(&input), 0);
+	}
+}
+";
+
+const ANSWERS_TEMPLATE: &str = "\
+# Expected answers, checked by `verify`
+# [example]
+# part1 = 0
+# part2 = 0
+#
+# [input]
+# part1 = 0
+# part2 = 0
+#
+# [inputs.NAME] for inputs/NAME.txt
+# part1 = 0
+# part2 = 0
+";
+
+pub fn new_day(root: &Path, data_root: &Path, day: usize) -> Result<Vec<String>> {
+	let module = root.join(format!("src/day{}.rs", day));
+	let main = root.join("src/main.rs");
+	let data = data_root.join(format!("day{}", day));
+	let path_string = |path: &Path| path.to_string_lossy().to_string();
+	if module.exists() {
+		return Err(Error::Refused(format!("{} already exists", path_string(&module))));
+	}
+	let main_text = fs::read_to_string(&main).map_err(|error| Error::io(&path_string(&main), error))?;
+	let registered = register(&main_text, day)
+		.ok_or_else(|| Error::missing_section(&path_string(&main), "mod dayN; and registry"))?;
+	let write = |path: &Path, text: &str| fs::write(path, text)
+		.map_err(|error| Error::io(&path_string(path), error));
+	fs::create_dir_all(&data).map_err(|error| Error::io(&path_string(&data), error))?;
+	write(&module, &MODULE_TEMPLATE.replace("{day}", &day.to_string()))?;
+	write(&data.join("example.txt"), "")?;
+	write(&data.join("answers.toml"), ANSWERS_TEMPLATE)?;
+	write(&main, &registered)?;
+	Ok(vec![path_string(&module),
+			path_string(&data.join("example.txt")),
+			path_string(&data.join("answers.toml")),
+			path_string(&main)])
+}
+
+fn register(main_text: &str, day: usize) -> Option<String> {
+	let lines: Vec<&str> = main_text.lines().collect();
+	let is_day_line = |line: &str, prefix: &str, suffix: &str| line.trim()
+		.strip_prefix(prefix)
+		.and_then(|rest| rest.split_once(suffix))
+		.is_some_and(|(number, _)| number.parse::<usize>().is_ok());
+	let last_mod = lines.iter().rposition(|line| is_day_line(line, "mod day", ";"))?;
+	let last_entry = lines.iter().rposition(|line| is_day_line(line, "Box::new(day", "::"))?;
+	let indentation: String = lines[last_entry].chars().take_while(|c| c.is_whitespace()).collect();
+	let mut result: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+	result.insert(last_entry + 1, format!("{}Box::new(day{}::Day{}),", indentation, day, day));
+	result.insert(last_mod + 1, format!("mod day{};", day));
+	Some(result.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod scaffold_tests {
+	use super::*;
+	use crate::test_support::TempDir;
+
+	const MAIN: &str = "\
+mod day1;
+mod day2;
+mod error;
+
+fn registry() -> Vec<Box<dyn Runnable>> {
+	vec![
+		Box::new(day1::Day1),
+		Box::new(day2::Day2),
+	]
+}
+";
+
+#[test]
+	fn register_inserts_mod_and_entry() {
+		assert_eq!(register(MAIN, 7).unwrap(), "\
+mod day1;
+mod day2;
+mod day7;
+mod error;
+
+fn registry() -> Vec<Box<dyn Runnable>> {
+	vec![
+		Box::new(day1::Day1),
+		Box::new(day2::Day2),
+		Box::new(day7::Day7),
+	]
+}
+");
+		assert_eq!(register("fn main() {}\n", 7), None);
+	}
+
+#[test]
+	fn new_day_writes_module_and_data() {
+		let directory = TempDir::new("scaffold");
+		let root = directory.path();
+		fs::create_dir_all(root.join("src")).unwrap();
+		fs::write(root.join("src/main.rs"), MAIN).unwrap();
+		let created = new_day(root, &root.join("problem_data"), 3).unwrap();
+		assert_eq!(created.len(), 4);
+		let module = fs::read_to_string(root.join("src/day3.rs")).unwrap();
+		assert!(module.contains("impl Solution for Day3 {"));
+		assert!(module.contains("const DAY: usize = 3;"));
+		assert!(root.join("problem_data/day3/example.txt").is_file());
+		assert!(fs::read_to_string(root.join("src/main.rs")).unwrap().contains("mod day3;"));
+		assert!(matches!(new_day(root, &root.join("problem_data"), 3), Err(Error::Refused(_))));
+	}
+}
@@ -0,0 +1,110 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::answer::{Answer, IntoAnswer};
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::render::Format;
+
+pub trait Solution {
+	const DAY: usize;
+	type Input;
+	type Answer1: IntoAnswer;
+	type Answer2: IntoAnswer;
+
+	fn parse(source: &Source) -> Result<Self::Input>;
+	fn part1(input: &Self::Input) -> Self::Answer1;
+	fn part2(input: &Self::Input) -> Self::Answer2;
+
+	fn example_file(_part: usize) -> String {
+		format!("day{}/example.txt", Self::DAY)
+	}
+
+	fn input_file() -> String {
+		format!("day{}/input.txt", Self::DAY)
+	}
+
+	fn answers_file() -> String {
+		format!("day{}/answers.toml", Self::DAY)
+	}
+
+	fn history_file() -> String {
+		format!("day{}/submissions.tsv", Self::DAY)
+	}
+
+	fn inputs_dir() -> String {
+		format!("day{}/inputs", Self::DAY)
+	}
+
+	fn render(_input: &Self::Input, _format: Format) -> Option<String> {
+		None
+	}
+}
+
+fn in_data_root(data_root: &Path, filename: &str) -> String {
+	data_root.join(filename).to_string_lossy().to_string()
+}
+
+pub struct Run {
+	pub answer: Answer,
+	pub parse_time: Duration,
+	pub solve_time: Duration
+}
+
+pub trait Runnable {
+	fn day(&self) -> usize;
+	fn example_file(&self, data_root: &Path, part: usize) -> String;
+	fn input_file(&self, data_root: &Path) -> String;
+	fn answers_file(&self, data_root: &Path) -> String;
+	fn history_file(&self, data_root: &Path) -> String;
+	fn inputs_dir(&self, data_root: &Path) -> String;
+	fn run_part(&self, part: usize, source: &Source) -> Result<Run>;
+	fn render(&self, source: &Source, format: Format) -> Result<Option<String>>;
+}
+
+impl<S: Solution> Runnable for S {
+	fn day(&self) -> usize {
+		S::DAY
+	}
+
+	fn example_file(&self, data_root: &Path, part: usize) -> String {
+		in_data_root(data_root, &S::example_file(part))
+	}
+
+	fn input_file(&self, data_root: &Path) -> String {
+		in_data_root(data_root, &S::input_file())
+	}
+
+	fn answers_file(&self, data_root: &Path) -> String {
+		in_data_root(data_root, &S::answers_file())
+	}
+
+	fn history_file(&self, data_root: &Path) -> String {
+		in_data_root(data_root, &S::history_file())
+	}
+
+	fn inputs_dir(&self, data_root: &Path) -> String {
+		in_data_root(data_root, &S::inputs_dir())
+	}
+
+	fn run_part(&self, part: usize, source: &Source) -> Result<Run> {
+		let start = Instant::now();
+		let input = S::parse(source)?;
+		let parse_time = start.elapsed();
+		let start = Instant::now();
+		let answer = match part {
+			1 => S::part1(&input).into_answer()?,
+			2 => S::part2(&input).into_answer()?,
+			_ => return Err(Error::NoSuchPart { day: S::DAY, part })
+		};
+		Ok(Run {
+			answer,
+			parse_time,
+			solve_time: start.elapsed()
+		})
+	}
+
+	fn render(&self, source: &Source, format: Format) -> Result<Option<String>> {
+		Ok(S::render(&S::parse(source)?, format))
+	}
+}
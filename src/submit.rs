@@ -0,0 +1,284 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::answer::Answer;
+use crate::client::Client;
+use crate::config::YEAR;
+use crate::error::{Error, Result};
+use crate::input::Source;
+
+const WRONG_ANSWER_DELAY_S: u64 = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+	Correct,
+	TooHigh,
+	TooLow,
+	Wrong,
+	Wait(u64),
+	WrongLevel,
+	Unknown
+}
+
+impl Verdict {
+	fn parse(response: &str) -> Self {
+		let wait = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+		if response.contains("That's the right answer") {
+			Verdict::Correct
+		} else if response.contains("That's not the right answer") {
+			if response.contains("your answer is too high") {
+				Verdict::TooHigh
+			} else if response.contains("your answer is too low") {
+				Verdict::TooLow
+			} else {
+				Verdict::Wrong
+			}
+		} else if let Some(captures) = wait.captures(response) {
+			let number = |index: usize| captures.get(index)
+				.map(|m| m.as_str().parse::<u64>().unwrap())
+				.unwrap_or(0);
+			Verdict::Wait(number(1) * 60 + number(2))
+		} else if response.contains("You don't seem to be solving the right level") {
+			Verdict::WrongLevel
+		} else {
+			Verdict::Unknown
+		}
+	}
+
+	fn from_token(token: &str) -> Option<Self> {
+		match token {
+			"correct" => Some(Verdict::Correct),
+			"too-high" => Some(Verdict::TooHigh),
+			"too-low" => Some(Verdict::TooLow),
+			"wrong" => Some(Verdict::Wrong),
+			"wrong-level" => Some(Verdict::WrongLevel),
+			"unknown" => Some(Verdict::Unknown),
+			wait => wait.strip_prefix("wait:")?.parse().ok().map(Verdict::Wait)
+		}
+	}
+
+	fn is_wrong(&self) -> bool {
+		matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+	}
+
+	fn delay(&self) -> u64 {
+		match self {
+			Verdict::Wait(seconds) => *seconds,
+			wrong if wrong.is_wrong() => WRONG_ANSWER_DELAY_S,
+			_ => 0
+		}
+	}
+}
+
+impl fmt::Display for Verdict {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Verdict::Correct => write!(f, "correct"),
+			Verdict::TooHigh => write!(f, "too-high"),
+			Verdict::TooLow => write!(f, "too-low"),
+			Verdict::Wrong => write!(f, "wrong"),
+			Verdict::Wait(seconds) => write!(f, "wait:{}", seconds),
+			Verdict::WrongLevel => write!(f, "wrong-level"),
+			Verdict::Unknown => write!(f, "unknown")
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+	pub time: u64,
+	pub part: usize,
+	pub answer: Answer,
+	pub verdict: Verdict
+}
+
+impl Attempt {
+	fn parse(line: &str) -> Result<Self> {
+		let fields: Vec<&str> = line.split('\t').collect();
+		if fields.len() != 4 {
+			return Err(Error::parse(line, line, "Expected <time>\t<part>\t<answer>\t<verdict>"));
+		}
+		Ok(Attempt {
+			time: fields[0].parse().map_err(|_| Error::parse(line, fields[0], "Not a time"))?,
+			part: fields[1].parse().map_err(|_| Error::parse(line, fields[1], "Not a part"))?,
+			answer: fields[2].parse().unwrap(),
+			verdict: Verdict::from_token(fields[3])
+				.ok_or_else(|| Error::parse(line, fields[3], "Not a verdict"))?
+		})
+	}
+}
+
+impl fmt::Display for Attempt {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}\t{}\t{}\t{}", self.time, self.part, self.answer, self.verdict)
+	}
+}
+
+pub struct History {
+	filename: String,
+	attempts: Vec<Attempt>
+}
+
+impl History {
+	pub fn read(filename: &str) -> Result<Self> {
+		let attempts = if Path::new(filename).is_file() {
+			Source::from_file(filename)?.parse_lines(Attempt::parse)?
+		} else {
+			Vec::new()
+		};
+		Ok(History {
+			filename: filename.to_string(),
+			attempts
+		})
+	}
+
+	pub fn check(&self, part: usize, answer: &Answer, now: u64) -> Result<()> {
+		let refuse = |message: String| Err(Error::Refused(message));
+		for attempt in self.attempts.iter().filter(|a| a.part == part) {
+			match (&attempt.verdict, &attempt.answer, answer) {
+				(Verdict::Correct, correct, _) =>
+					return refuse(format!("part {} was already solved with {}", part, correct)),
+				(verdict, previous, _) if verdict.is_wrong() && previous == answer =>
+					return refuse(format!("{} was already rejected as {}", answer, verdict)),
+				(Verdict::TooHigh, Answer::Integer(high), Answer::Integer(value)) if value > high =>
+					return refuse(format!("{} is above {} which was too high", value, high)),
+				(Verdict::TooLow, Answer::Integer(low), Answer::Integer(value)) if value < low =>
+					return refuse(format!("{} is below {} which was too low", value, low)),
+				_ => {}
+			}
+		}
+		if let Some(last) = self.attempts.last() {
+			let allowed_at = last.time + last.verdict.delay();
+			if now < allowed_at {
+				return refuse(format!("wait {}s before submitting again", allowed_at - now));
+			}
+		}
+		Ok(())
+	}
+
+	pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+		let io_error = |error| Error::io(&self.filename, error);
+		if let Some(directory) = Path::new(&self.filename).parent() {
+			fs::create_dir_all(directory).map_err(io_error)?;
+		}
+		let mut file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&self.filename)
+			.map_err(io_error)?;
+		writeln!(file, "{}", attempt).map_err(io_error)?;
+		self.attempts.push(attempt);
+		Ok(())
+	}
+}
+
+pub fn submit(client: &Client, history: &mut History, day: usize, part: usize,
+			  answer: &Answer, now: u64) -> Result<Verdict> {
+	history.check(part, answer, now)?;
+	let response = client.post_form(&format!("/{}/day/{}/answer", YEAR, day),
+									&[("level", &part.to_string()),
+									  ("answer", &answer.to_string())])?;
+	let verdict = Verdict::parse(&response);
+	history.record(Attempt {
+		time: now,
+		part,
+		answer: answer.clone(),
+		verdict: verdict.clone()
+	})?;
+	Ok(verdict)
+}
+
+#[cfg(test)]
+mod submit_tests {
+	use super::*;
+	use crate::client::test_server;
+	use crate::config::Config;
+	use crate::test_support::TempDir;
+
+	fn history(attempts: Vec<Attempt>) -> History {
+		History { filename: String::new(), attempts }
+	}
+
+	fn attempt(time: u64, part: usize, answer: i64, verdict: Verdict) -> Attempt {
+		Attempt { time, part, answer: Answer::Integer(answer), verdict }
+	}
+
+#[test]
+	fn verdicts_from_responses() {
+		assert_eq!(Verdict::parse("<p>That's the right answer! You are one gold star"),
+				   Verdict::Correct);
+		assert_eq!(Verdict::parse("That's not the right answer; your answer is too high."),
+				   Verdict::TooHigh);
+		assert_eq!(Verdict::parse("That's not the right answer; your answer is too low."),
+				   Verdict::TooLow);
+		assert_eq!(Verdict::parse("That's not the right answer. If you're stuck"),
+				   Verdict::Wrong);
+		assert_eq!(Verdict::parse("You gave an answer too recently. You have 1m 5s left to wait."),
+				   Verdict::Wait(65));
+		assert_eq!(Verdict::parse("You have 42s left to wait."), Verdict::Wait(42));
+		assert_eq!(Verdict::parse("You don't seem to be solving the right level."),
+				   Verdict::WrongLevel);
+		assert_eq!(Verdict::parse("<html></html>"), Verdict::Unknown);
+	}
+
+#[test]
+	fn attempts_round_trip() {
+		let recorded = attempt(1701820800, 2, 46, Verdict::Wait(30));
+		assert_eq!(Attempt::parse(&recorded.to_string()).unwrap(), recorded);
+		assert!(Attempt::parse("1701820800\t2\t46").is_err());
+	}
+
+#[test]
+	fn refuses_known_wrong_and_out_of_bounds() {
+		let history = history(vec![attempt(0, 1, 100, Verdict::TooHigh),
+								   attempt(100, 1, 10, Verdict::TooLow),
+								   attempt(200, 1, 50, Verdict::Wrong)]);
+		let check = |value: i64| history.check(1, &Answer::Integer(value), 1000);
+		assert!(check(100).is_err());
+		assert!(check(101).is_err());
+		assert!(check(9).is_err());
+		assert!(check(50).is_err());
+		assert!(check(51).is_ok());
+		assert!(history.check(2, &Answer::Integer(500), 1000).is_ok());
+	}
+
+#[test]
+	fn refuses_solved_parts_and_throttles() {
+		let history = history(vec![attempt(0, 1, 35, Verdict::Correct),
+								   attempt(100, 2, 40, Verdict::Wait(30))]);
+		assert!(history.check(1, &Answer::Integer(36), 1000).is_err());
+		assert!(history.check(2, &Answer::Integer(46), 120).is_err());
+		assert!(history.check(2, &Answer::Integer(46), 130).is_ok());
+		let history = self::history(vec![attempt(100, 2, 40, Verdict::TooLow)]);
+		assert!(history.check(2, &Answer::Integer(46), 159).is_err());
+		assert!(history.check(2, &Answer::Integer(46), 160).is_ok());
+	}
+
+#[test]
+	fn submit_posts_and_records() {
+		let (base_url, requests) = test_server::serve(vec![
+			(200, "<main>That's not the right answer; your answer is too low.</main>".to_string())
+		]);
+		let client = Client::new(&Config {
+			session: Some("abc".to_string()),
+			base_url,
+			..Config::default()
+		});
+		let directory = TempDir::new("submit");
+		let filename = directory.path().join("history.tsv");
+		let filename = filename.to_str().unwrap();
+		let mut history = History::read(filename).unwrap();
+		let verdict = submit(&client, &mut history, 5, 2, &Answer::Integer(46), 1000).unwrap();
+		assert_eq!(verdict, Verdict::TooLow);
+		let request = requests.recv().unwrap();
+		assert!(request.starts_with("POST /2023/day/5/answer HTTP/1.1"), "{}", request);
+		assert!(request.ends_with("level=2&answer=46"), "{}", request);
+		let reread = History::read(filename).unwrap();
+		assert_eq!(reread.attempts, vec![attempt(1000, 2, 46, Verdict::TooLow)]);
+		assert!(submit(&client, &mut history, 5, 2, &Answer::Integer(40), 2000).is_err());
+	}
+}
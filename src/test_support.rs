@@ -0,0 +1,37 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::interval::IntervalSet;
+
+/// A directory under the system temp directory that is deleted again when dropped, so a
+/// failing assert does not leave it behind. `name` must be unique among the tests.
+pub struct TempDir {
+	path: PathBuf
+}
+
+impl TempDir {
+	pub fn new(name: &str) -> Self {
+		let path = env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+		let _ = fs::remove_dir_all(&path);
+		fs::create_dir_all(&path).unwrap();
+		TempDir { path }
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+}
+
+impl Drop for TempDir {
+	fn drop(&mut self) {
+		let _ = fs::remove_dir_all(&self.path);
+	}
+}
+
+/// Every value in the set, as a model to check interval arithmetic against.
+pub fn values(set: &IntervalSet) -> BTreeSet<usize> {
+	set.iter().flat_map(|interval| interval.start..interval.end).collect()
+}
@@ -0,0 +1,100 @@
+use std::fs;
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+	Integer(i64),
+	Text(String)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+	pub section: Option<String>,
+	pub key: String,
+	pub value: Value,
+	pub line: usize,
+	pub column: usize
+}
+
+pub fn read(filename: &str) -> Result<Vec<Entry>> {
+	let text = fs::read_to_string(filename).map_err(|error| Error::io(filename, error))?;
+	parse(filename, &text)
+}
+
+pub fn parse(filename: &str, text: &str) -> Result<Vec<Entry>> {
+	let mut entries = Vec::new();
+	let mut section: Option<String> = None;
+	for (index, raw_line) in text.lines().enumerate() {
+		let line = strip_comment(raw_line).trim();
+		if line.is_empty() {
+			continue;
+		}
+		let error = |text: &str, message: &str| Error::parse(raw_line, text, message)
+			.at(filename, index + 1);
+		if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+			section = Some(name.trim().to_string());
+			continue;
+		}
+		let (key, value) = line.split_once('=')
+			.ok_or_else(|| error(line, "Expected key = value"))?;
+		entries.push(Entry {
+			section: section.clone(),
+			key: key.trim().to_string(),
+			value: parse_value(value.trim()).ok_or_else(|| error(value.trim(), "Not a value"))?,
+			line: index + 1,
+			column: raw_line.len() - raw_line.trim_start().len() + 1
+		});
+	}
+	Ok(entries)
+}
+
+fn strip_comment(line: &str) -> &str {
+	let mut in_string = false;
+	for (index, c) in line.char_indices() {
+		match c {
+			'"' => in_string = !in_string,
+			'#' if !in_string => return &line[..index],
+			_ => {}
+		}
+	}
+	line
+}
+
+fn parse_value(value: &str) -> Option<Value> {
+	if let Some(text) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+		Some(Value::Text(text.to_string()))
+	} else {
+		value.replace('_', "").parse::<i64>().ok().map(Value::Integer)
+	}
+}
+
+#[cfg(test)]
+mod toml_lite_tests {
+	use super::*;
+
+#[test]
+	fn sections_keys_and_values() {
+		let entries = parse("a.toml", "\
+top = 1
+[example] # the example
+part1 = 1_000
+  part2 = \"a # not a comment\"
+").unwrap();
+		assert_eq!(entries, vec![
+			Entry { section: None, key: "top".to_string(), value: Value::Integer(1), line: 1, column: 1 },
+			Entry { section: Some("example".to_string()), key: "part1".to_string(),
+					value: Value::Integer(1000), line: 3, column: 1 },
+			Entry { section: Some("example".to_string()), key: "part2".to_string(),
+					value: Value::Text("a # not a comment".to_string()), line: 4, column: 3 }
+		]);
+	}
+
+#[test]
+	fn bad_lines_are_located() {
+		assert_eq!(parse("a.toml", "\n[s]\nkey\n").err().unwrap().to_string(),
+				   "a.toml:3:1: Expected key = value: \"key\"");
+		assert_eq!(parse("a.toml", "key = twelve").err().unwrap().to_string(),
+				   "a.toml:1:7: Not a value: \"twelve\"");
+	}
+}
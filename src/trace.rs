@@ -0,0 +1,59 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::interval::Interval;
+
+static VERBOSITY: AtomicUsize = AtomicUsize::new(0);
+static FOCUS: Mutex<Option<Interval>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Level {
+	Stages = 1,
+	Details = 2
+}
+
+pub fn set_verbosity(verbosity: usize) {
+	VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+	VERBOSITY.load(Ordering::Relaxed) >= level as usize
+}
+
+pub fn set_focus(focus: Option<Interval>) {
+	*FOCUS.lock().unwrap() = focus;
+}
+
+/// The part of `interval` that should be traced, everything when no focus is set.
+pub fn in_focus(interval: Interval) -> Option<Interval> {
+	match *FOCUS.lock().unwrap() {
+		Some(focus) => focus.intersection(&interval),
+		None if interval.is_empty() => None,
+		None => Some(interval)
+	}
+}
+
+macro_rules! trace {
+	($level:expr, $($arg:tt)*) => {
+		if $crate::trace::enabled($level) {
+			eprintln!($($arg)*);
+		}
+	}
+}
+
+pub(crate) use trace;
+
+#[cfg(test)]
+mod trace_tests {
+	use super::*;
+
+#[test]
+	fn focus_restricts_intervals() {
+		set_focus(Some(Interval::new(10, 20)));
+		assert_eq!(in_focus(Interval::new(15, 30)), Some(Interval::new(15, 20)));
+		assert_eq!(in_focus(Interval::new(20, 30)), None);
+		set_focus(None);
+		assert_eq!(in_focus(Interval::new(20, 30)), Some(Interval::new(20, 30)));
+		assert!(!enabled(Level::Stages));
+	}
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::answer::Answer;
+use crate::data;
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::solution::Runnable;
+use crate::toml_lite::{self, Entry, Value};
+
+pub struct ExpectedAnswers {
+	answers: HashMap<(String, usize), Answer>
+}
+
+impl ExpectedAnswers {
+	pub fn read(filename: &str) -> Result<Self> {
+		Self::from_entries(filename, toml_lite::read(filename)?)
+	}
+
+	fn from_entries(filename: &str, entries: Vec<Entry>) -> Result<Self> {
+		let mut answers = HashMap::new();
+		for entry in entries {
+			let part = match entry.key.as_str() {
+				"part1" => 1,
+				"part2" => 2,
+				_ => return Err(Error::parse_at_column(entry.column, &entry.key, "Expected part1 or part2")
+								.at(filename, entry.line))
+			};
+			let source = entry.section.ok_or_else(|| 
+				Error::parse_at_column(entry.column, &entry.key, "Answer outside of a [section]")
+					.at(filename, entry.line))?;
+			let answer = match entry.value {
+				Value::Integer(value) => Answer::Integer(value),
+				Value::Text(text) => Answer::Text(text)
+			};
+			answers.insert((source, part), answer);
+		}
+		Ok(ExpectedAnswers { answers })
+	}
+
+	pub fn get(&self, source: &str, part: usize) -> Option<&Answer> {
+		self.answers.get(&(source.to_string(), part))
+	}
+}
+
+/// No answers file means nothing is recorded yet, but one that can't be read is an error.
+fn read_expected(filename: &str) -> Result<Option<ExpectedAnswers>> {
+	if Path::new(filename).is_file() {
+		ExpectedAnswers::read(filename).map(Some)
+	} else {
+		Ok(None)
+	}
+}
+
+pub enum Outcome {
+	Pass(Answer),
+	Regression { expected: Answer, actual: Answer },
+	Unrecorded(Answer),
+	Missing(String),
+	Error(String)
+}
+
+impl fmt::Display for Outcome {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Outcome::Pass(answer) => write!(f, "pass ({})", answer),
+			Outcome::Regression { expected, actual } =>
+				write!(f, "REGRESSION expected {}, got {}", expected, actual),
+			Outcome::Unrecorded(answer) => write!(f, "unrecorded ({})", answer),
+			Outcome::Missing(filename) => write!(f, "skipped, {} is missing", filename),
+			Outcome::Error(message) => write!(f, "ERROR {}", message)
+		}
+	}
+}
+
+pub struct Check {
+	pub day: usize,
+	pub part: usize,
+	pub source: String,
+	pub outcome: Outcome
+}
+
+pub fn verify(solution: &dyn Runnable, data_root: &Path, parts: &[usize]) -> Vec<Check> {
+	let mut checks = Vec::new();
+	let expected = match read_expected(&solution.answers_file(data_root)) {
+		Ok(expected) => expected,
+		Err(error) => {
+			let outcome = Outcome::Error(error.to_string());
+			checks.push(Check { day: solution.day(), part: 0, source: "answers".to_string(), outcome });
+			None
+		}
+	};
+	let named_inputs = match data::named_inputs(solution, data_root) {
+		Ok(named_inputs) => named_inputs,
+		Err(error) => {
+			let outcome = Outcome::Error(error.to_string());
+			checks.push(Check { day: solution.day(), part: 0, source: "inputs".to_string(), outcome });
+			Vec::new()
+		}
+	};
+	for part in parts {
+		let mut sources = vec![("example".to_string(), solution.example_file(data_root, *part)),
+							   ("input".to_string(), solution.input_file(data_root))];
+		sources.extend(named_inputs.iter().map(|input| (input.name.clone(), input.filename.clone())));
+		for (source, filename) in sources {
+			if !Path::new(&filename).is_file() {
+				let outcome = Outcome::Missing(filename);
+				checks.push(Check { day: solution.day(), part: *part, source, outcome });
+				continue;
+			}
+			let run = Source::from_file(&filename).and_then(|input| solution.run_part(*part, &input));
+			let outcome = match (run, &expected) {
+				(Err(error), _) => Outcome::Error(error.to_string()),
+				(Ok(run), Some(expected)) => match expected.get(&source, *part) {
+					Some(answer) if *answer == run.answer => Outcome::Pass(run.answer),
+					Some(answer) => Outcome::Regression {
+						expected: answer.clone(),
+						actual: run.answer
+					},
+					None => Outcome::Unrecorded(run.answer)
+				},
+				(Ok(run), None) => Outcome::Unrecorded(run.answer)
+			};
+			checks.push(Check { day: solution.day(), part: *part, source, outcome });
+		}
+	}
+	checks
+}
+
+#[cfg(test)]
+mod verify_tests {
+	use super::*;
+	use std::fs;
+	use crate::day6::Day6;
+	use crate::test_support::TempDir;
+
+	fn parse(text: &str) -> Result<ExpectedAnswers> {
+		ExpectedAnswers::from_entries("answers.toml", toml_lite::parse("answers.toml", text)?)
+	}
+
+#[test]
+	fn parse_sections_and_values() {
+		let expected = parse("\
+# recorded answers
+[example]
+part1 = 142
+part2 = \"EZFCHJAB\"
+
+[input]
+part1 = 54_630 # trailing comment
+").unwrap();
+		assert_eq!(expected.get("example", 1), Some(&Answer::Integer(142)));
+		assert_eq!(expected.get("example", 2), Some(&Answer::Text("EZFCHJAB".to_string())));
+		assert_eq!(expected.get("input", 1), Some(&Answer::Integer(54630)));
+		assert_eq!(expected.get("input", 2), None);
+	}
+
+#[test]
+	fn parse_errors_report_line() {
+		let error = parse("[example]\npart3 = 1\n").err().unwrap();
+		assert_eq!(error.to_string(), "answers.toml:2:1: Expected part1 or part2: \"part3\"");
+		let error = parse("[example]\n\tpart3 = 1\n").err().unwrap();
+		assert_eq!(error.to_string(), "answers.toml:2:2: Expected part1 or part2: \"part3\"");
+		assert!(parse("part1 = 1\n").is_err());
+		assert!(parse("[input]\npart1 = twelve\n").is_err());
+	}
+
+#[test]
+	fn only_missing_answers_files_are_unrecorded() {
+		assert!(read_expected("problem_data/day99/answers.toml").unwrap().is_none());
+		assert!(read_expected("problem_data/day1/answers.toml").unwrap().is_some());
+		let error = read_expected("problem_data/day1/example_part1.txt").err().unwrap().to_string();
+		assert!(error.starts_with("problem_data/day1/example_part1.txt:1:1: "), "{}", error);
+	}
+
+#[test]
+	fn verifies_every_named_input() {
+		let directory = TempDir::new("verify");
+		let data_root = directory.path();
+		let day = data_root.join("day6");
+		fs::create_dir_all(day.join("inputs")).unwrap();
+		fs::write(day.join("example.txt"), "Time: 7 15 30\nDistance: 9 40 200\n").unwrap();
+		fs::write(day.join("inputs/alice.txt"), "Time: 7\nDistance: 9\n").unwrap();
+		fs::write(day.join("inputs/bob.txt"), "Time: 30\nDistance: 200\n").unwrap();
+		fs::write(day.join("answers.toml"), "\
+[example]
+part1 = 288
+[inputs.alice]
+part1 = 4
+[inputs.bob]
+part1 = 10
+").unwrap();
+		let checks = verify(&Day6, data_root, &[1]);
+		let outcomes: Vec<String> = checks.iter()
+			.map(|check| format!("{} {}", check.source, check.outcome))
+			.collect();
+		assert_eq!(outcomes, vec![
+			"example pass (288)".to_string(),
+			format!("input skipped, {} is missing", day.join("input.txt").display()),
+			"inputs.alice pass (4)".to_string(),
+			"inputs.bob REGRESSION expected 10, got 9".to_string()
+		]);
+	}
+
+#[test]
+	fn malformed_answers_are_errors() {
+		let directory = TempDir::new("verify-malformed");
+		let data_root = directory.path();
+		let day = data_root.join("day6");
+		fs::create_dir_all(&day).unwrap();
+		fs::write(day.join("example.txt"), "Time: 7 15 30\nDistance: 9 40 200\n").unwrap();
+		fs::write(day.join("answers.toml"), "[example]\npart1 = 143\npart3 = 2\n").unwrap();
+		let checks = verify(&Day6, data_root, &[1]);
+		assert_eq!((checks[0].part, checks[0].source.as_str()), (0, "answers"));
+		assert!(matches!(&checks[0].outcome, Outcome::Error(message) if message.contains("part3")));
+		assert!(matches!(checks[1].outcome, Outcome::Unrecorded(_)));
+	}
+}